@@ -0,0 +1,53 @@
+//! Throughput of the SSE decoder on a realistic `message.part.delta` stream.
+
+use std::{fmt::Write, hint::black_box};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use opencode_sdk_rs::streaming::SseDecoder;
+
+/// Build `count` delta events, alternating ASCII and multi-byte text.
+fn payload(count: usize) -> Vec<u8> {
+    let mut out = String::new();
+    for i in 0..count {
+        let delta = if i % 2 == 0 { "streaming tokens " } else { "héllo wörld ✓ 🚀 " };
+        // Writing to a `String` cannot fail.
+        let _ = write!(
+            out,
+            "id: {i}\r\nevent: message\r\ndata: {{\"type\":\"message.part.delta\",\"properties\":{{\"sessionID\":\"ses_1\",\"messageID\":\"msg_1\",\"partID\":\"prt_1\",\"field\":\"text\",\"delta\":\"{delta}\"}}}}\r\n\r\n"
+        );
+        if i % 16 == 0 {
+            out.push_str(": keep-alive\r\n\r\n");
+        }
+    }
+    out.into_bytes()
+}
+
+fn decode(bytes: &[u8], chunk_size: usize) -> usize {
+    let mut decoder = SseDecoder::new();
+    let mut events = 0;
+    for chunk in bytes.chunks(chunk_size) {
+        decoder.feed(chunk);
+        while let Some(event) = decoder.next_event() {
+            black_box(event);
+            events += 1;
+        }
+    }
+    decoder.finish();
+    events + std::iter::from_fn(|| decoder.next_event()).count()
+}
+
+fn bench_decoder(c: &mut Criterion) {
+    let bytes = payload(1_000);
+    let mut group = c.benchmark_group("sse_decoder");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    // 7 bytes splits most multi-byte characters and CRLF pairs across chunks.
+    for chunk_size in [7, 64, 1024, bytes.len()] {
+        group.bench_with_input(BenchmarkId::from_parameter(chunk_size), &chunk_size, |b, &size| {
+            b.iter(|| decode(black_box(&bytes), size));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_decoder);
+criterion_main!(benches);
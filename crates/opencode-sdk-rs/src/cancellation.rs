@@ -0,0 +1,124 @@
+//! Cooperative cancellation for in-flight requests.
+//!
+//! Pass a [`CancellationToken`] through [`crate::RequestOptions::cancel`] and
+//! call [`CancellationToken::cancel`] from anywhere (a "stop" button, a
+//! shutdown signal, …).  The request in progress — including any retry
+//! back-off sleep — is dropped and the call returns
+//! [`crate::OpencodeError::UserAbort`].
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use tokio::sync::Notify;
+
+use crate::error::OpencodeError;
+
+/// A cheaply cloneable handle used to cancel one or more requests.
+///
+/// All clones share the same state: cancelling any of them cancels every
+/// request that was given a clone.  Once cancelled, a token stays cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every request using this token.
+    pub fn cancel(&self) {
+        if !self.inner.cancelled.swap(true, Ordering::AcqRel) {
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Wait until the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            // Register interest before checking the flag so a concurrent
+            // `cancel` cannot slip in between.
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Drive `future` to completion unless the token is cancelled first, in
+    /// which case `future` is dropped and [`OpencodeError::UserAbort`] is
+    /// returned.
+    pub(crate) async fn run<F: Future>(&self, future: F) -> Result<F::Output, OpencodeError> {
+        tokio::select! {
+            biased;
+            () = self.cancelled() => Err(OpencodeError::UserAbort),
+            output = future => Ok(output),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn cancel_is_shared_and_sticky() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancelled_resolves_after_cancel() {
+        let token = CancellationToken::new();
+        let waiter = {
+            let token = token.clone();
+            tokio::spawn(async move { token.cancelled().await })
+        };
+        tokio::task::yield_now().await;
+        token.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn run_returns_user_abort_when_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = token.run(std::future::pending::<()>()).await;
+        assert!(matches!(result, Err(OpencodeError::UserAbort)));
+    }
+
+    #[tokio::test]
+    async fn run_passes_output_through() {
+        let token = CancellationToken::new();
+        assert_eq!(token.run(async { 7 }).await.unwrap(), 7);
+    }
+}
@@ -1,9 +1,17 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use http::{HeaderMap, header::HeaderValue};
 use serde::{Serialize, de::DeserializeOwned};
 
-use crate::{config::ClientOptions, error::OpencodeError, resources::app::AppResource};
+use crate::{
+    cancellation::CancellationToken,
+    config::ClientOptions,
+    error::OpencodeError,
+    middleware::{Middleware, MiddlewareRequest, MiddlewareResponse},
+    resources::app::AppResource,
+    url::QueryPairs,
+    websocket::{WebSocketReader, WebSocketWriter},
+};
 
 /// SDK version from `Cargo.toml`, used in the `User-Agent` header.
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -20,6 +28,21 @@ pub struct RequestOptions {
     pub timeout: Option<Duration>,
     /// Override the maximum number of retries.
     pub max_retries: Option<u32>,
+    /// Extra query parameters for this request only.
+    ///
+    /// Keys may repeat.  A key listed here replaces every value the client's
+    /// default query or the endpoint's own parameters set for that key.
+    pub query: Option<Vec<(String, String)>>,
+    /// Target this project directory for this request only, overriding the
+    /// directory of a [scoped](Opencode::scoped) client.
+    pub directory: Option<String>,
+    /// Cancel the request, including any retry back-off, when this token is
+    /// cancelled.  The call then fails with [`OpencodeError::UserAbort`].
+    pub cancel: Option<CancellationToken>,
+    /// When a session request that runs the agent (`chat`, `command`,
+    /// `shell`) is cancelled through [`cancel`](Self::cancel), also ask the
+    /// server to abort the session.
+    pub abort_on_cancel: bool,
 }
 
 /// The main `OpenCode` SDK client.
@@ -33,6 +56,8 @@ pub struct Opencode {
     max_retries: u32,
     default_headers: HeaderMap,
     default_query: HashMap<String, String>,
+    directory: Option<String>,
+    middleware: Arc<[Arc<dyn Middleware>]>,
     pub(crate) http_client: hpx::Client,
 }
 
@@ -44,6 +69,8 @@ impl std::fmt::Debug for Opencode {
             .field("max_retries", &self.max_retries)
             .field("default_headers", &self.default_headers)
             .field("default_query", &self.default_query)
+            .field("directory", &self.directory)
+            .field("middleware", &self.middleware.len())
             .field("http_client", &"hpx::Client { .. }")
             .finish()
     }
@@ -85,14 +112,27 @@ impl Opencode {
             max_retries: opts.resolve_max_retries(),
             default_headers,
             default_query: opts.resolve_default_query(),
+            directory: None,
+            middleware: Arc::new([]),
             http_client,
         })
     }
 
+    /// Return a view of this client that targets `directory` on the server.
+    ///
+    /// A single server can serve several project directories.  The returned
+    /// client sends `directory` with every request, including event streams,
+    /// and shares the underlying connection pool with `self`.
+    /// [`RequestOptions::directory`] still overrides it per call.
+    #[must_use]
+    pub fn scoped(&self, directory: impl Into<String>) -> Self {
+        Self { directory: Some(directory.into()), ..self.clone() }
+    }
+
     /// Return an [`OpencodeBuilder`] for fluent configuration.
     #[must_use]
     pub fn builder() -> OpencodeBuilder {
-        OpencodeBuilder { options: ClientOptions::default() }
+        OpencodeBuilder { options: ClientOptions::default(), middleware: Vec::new() }
     }
 
     // ── Getters ────────────────────────────────────────────────────
@@ -127,13 +167,34 @@ impl Opencode {
         &self.default_query
     }
 
+    /// The project directory this client is scoped to, if any.
+    #[must_use]
+    pub fn directory(&self) -> Option<&str> {
+        self.directory.as_deref()
+    }
+
     // ── Resource accessors ─────────────────────────────────────
 
+    /// Access the Agent resource.
+    pub const fn agent(&self) -> crate::resources::agent::AgentResource<'_> {
+        crate::resources::agent::AgentResource::new(self)
+    }
+
     /// Access the App resource.
     pub const fn app(&self) -> AppResource<'_> {
         AppResource::new(self)
     }
 
+    /// Access the Auth resource.
+    pub const fn auth(&self) -> crate::resources::auth::AuthResource<'_> {
+        crate::resources::auth::AuthResource::new(self)
+    }
+
+    /// Access the Command resource.
+    pub const fn command(&self) -> crate::resources::command::CommandResource<'_> {
+        crate::resources::command::CommandResource::new(self)
+    }
+
     /// Access the Config resource.
     pub const fn config(&self) -> crate::resources::config::ConfigResource<'_> {
         crate::resources::config::ConfigResource::new(self)
@@ -154,39 +215,108 @@ impl Opencode {
         crate::resources::find::FindResource::new(self)
     }
 
+    /// Access the Formatter resource.
+    pub const fn formatter(&self) -> crate::resources::formatter::FormatterResource<'_> {
+        crate::resources::formatter::FormatterResource::new(self)
+    }
+
+    /// Access the Lsp resource.
+    pub const fn lsp(&self) -> crate::resources::lsp::LspResource<'_> {
+        crate::resources::lsp::LspResource::new(self)
+    }
+
+    /// Access the Mcp resource.
+    pub const fn mcp(&self) -> crate::resources::mcp::McpResource<'_> {
+        crate::resources::mcp::McpResource::new(self)
+    }
+
+    /// Access the Permission resource.
+    pub const fn permission(&self) -> crate::resources::permission::PermissionResource<'_> {
+        crate::resources::permission::PermissionResource::new(self)
+    }
+
+    /// Access the Project resource.
+    pub const fn project(&self) -> crate::resources::project::ProjectResource<'_> {
+        crate::resources::project::ProjectResource::new(self)
+    }
+
+    /// Access the Provider resource.
+    pub const fn provider(&self) -> crate::resources::provider::ProviderResource<'_> {
+        crate::resources::provider::ProviderResource::new(self)
+    }
+
+    /// Access the Pty resource.
+    pub const fn pty(&self) -> crate::resources::pty::PtyResource<'_> {
+        crate::resources::pty::PtyResource::new(self)
+    }
+
+    /// Access the Question resource.
+    pub const fn question(&self) -> crate::resources::question::QuestionResource<'_> {
+        crate::resources::question::QuestionResource::new(self)
+    }
+
     /// Access the Session resource.
     pub const fn session(&self) -> crate::resources::session::SessionResource<'_> {
         crate::resources::session::SessionResource::new(self)
     }
 
+    /// Access the Skill resource.
+    pub const fn skill(&self) -> crate::resources::skill::SkillResource<'_> {
+        crate::resources::skill::SkillResource::new(self)
+    }
+
     /// Access the Tui resource.
     pub const fn tui(&self) -> crate::resources::tui::TuiResource<'_> {
         crate::resources::tui::TuiResource::new(self)
     }
 
+    /// Access the Vcs resource.
+    pub const fn vcs(&self) -> crate::resources::vcs::VcsResource<'_> {
+        crate::resources::vcs::VcsResource::new(self)
+    }
+
+    /// Access the Worktree resource.
+    pub const fn worktree(&self) -> crate::resources::worktree::WorktreeResource<'_> {
+        crate::resources::worktree::WorktreeResource::new(self)
+    }
+
     // ── URL & Header Building ──────────────────────────────────
 
-    /// Build a full URL by joining `base_url` + `path`, then appending
-    /// `default_query` and any extra `query` parameters.
+    /// Build a full URL by joining `base_url` + `path`, then appending the
+    /// percent-encoded query.
     ///
-    /// Query keys are sorted for deterministic output.
-    pub(crate) fn build_url(&self, path: &str, query: Option<&HashMap<String, String>>) -> String {
+    /// The query is layered by precedence: `default_query` (sorted by key for
+    /// deterministic output), then the `directory` (from
+    /// [`RequestOptions::directory`] or the [scope](Opencode::scoped)), then
+    /// the endpoint's `query`, then [`RequestOptions::query`].  A key in a later layer replaces all
+    /// of its values from earlier layers.  `path` is used verbatim, so interpolated
+    /// segments must already be encoded (see the `encode_path!` macro).
+    pub(crate) fn build_url(
+        &self,
+        path: &str,
+        query: &QueryPairs,
+        options: Option<&RequestOptions>,
+    ) -> String {
         let base = self.base_url.trim_end_matches('/');
         let path_part = if path.starts_with('/') { path.to_owned() } else { format!("/{path}") };
 
-        let mut params: Vec<(&str, &str)> =
-            self.default_query.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
-
-        if let Some(q) = query {
-            params.extend(q.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        let mut defaults: Vec<_> = self.default_query.iter().collect();
+        defaults.sort();
+        let mut params: QueryPairs =
+            defaults.into_iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        let directory = options.and_then(|o| o.directory.as_deref()).or(self.directory.as_deref());
+        if let Some(directory) = directory {
+            params.override_with(&[("directory".to_owned(), directory.to_owned())]);
+        }
+        params.override_with(query);
+        if let Some(overrides) = options.and_then(|o| o.query.as_ref()) {
+            params.override_with(overrides);
         }
 
         if params.is_empty() {
             format!("{base}{path_part}")
         } else {
-            params.sort_by_key(|(k, _)| *k);
-            let qs = params.iter().map(|(k, v)| format!("{k}={v}")).collect::<Vec<_>>().join("&");
-            format!("{base}{path_part}?{qs}")
+            format!("{base}{path_part}?{}", params.encode())
         }
     }
 
@@ -228,7 +358,8 @@ impl Opencode {
     ///
     /// The caller supplies a pre-serialised `body` (as [`serde_json::Value`])
     /// and an optional serialisable `query` struct.  On success the JSON
-    /// response is deserialised into `T`.
+    /// response is deserialised into `T`.  The whole exchange is dropped if
+    /// [`RequestOptions::cancel`] fires.
     async fn make_request<T, Q>(
         &self,
         method: http::Method,
@@ -241,7 +372,28 @@ impl Opencode {
         T: DeserializeOwned,
         Q: Serialize + Sync + ?Sized,
     {
-        let url = self.build_url(path, None);
+        let request = self.send_with_retries(method, path, body, query, options);
+        match options.and_then(|o| o.cancel.as_ref()) {
+            Some(token) => token.run(request).await?,
+            None => request.await,
+        }
+    }
+
+    /// The retry loop behind [`make_request`](Self::make_request).
+    async fn send_with_retries<T, Q>(
+        &self,
+        method: http::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+        query: Option<&Q>,
+        options: Option<&RequestOptions>,
+    ) -> Result<T, OpencodeError>
+    where
+        T: DeserializeOwned,
+        Q: Serialize + Sync + ?Sized,
+    {
+        let query = query.map(QueryPairs::from_serialize).transpose()?.unwrap_or_default();
+        let url = self.build_url(path, &query, options);
         let max_retries = options.and_then(|o| o.max_retries).unwrap_or(self.max_retries);
         let timeout = options.and_then(|o| o.timeout).unwrap_or(self.timeout);
         let extra_headers = options.and_then(|o| o.extra_headers.as_ref());
@@ -249,44 +401,53 @@ impl Opencode {
         let mut last_error: Option<OpencodeError> = None;
 
         for attempt in 0..=max_retries {
-            let headers = self.build_headers(extra_headers, attempt);
+            let mut request = MiddlewareRequest {
+                method: method.clone(),
+                url: url.clone(),
+                headers: self.build_headers(extra_headers, attempt),
+                body: body.clone(),
+                attempt,
+            };
+            self.run_request_middleware(&mut request).await?;
 
             tracing::debug!(
-                method = %method,
-                url = %url,
+                method = %request.method,
+                url = %request.url,
                 attempt,
                 "sending request"
             );
 
-            let mut req =
-                self.http_client.request(method.clone(), &url).headers(headers).timeout(timeout);
-
-            if let Some(q) = query {
-                req = req.query(q);
-            }
-
-            if let Some(ref b) = body {
-                req = req.json(b);
-            }
-
-            let result = req.send().await;
+            let result = self.send(&request).timeout(timeout).send().await;
 
             match result {
                 Ok(resp) => {
                     let status = resp.status();
-                    let resp_headers = resp.headers().clone();
+                    let headers = resp.headers().clone();
+
+                    let body = if status.is_success() {
+                        Some(resp.bytes().await.map_err(|e| OpencodeError::Http(Box::new(e)))?)
+                    } else {
+                        resp.bytes().await.ok()
+                    };
+                    let mut response = MiddlewareResponse { status, headers, body };
+                    self.run_response_middleware(&request, &mut response).await?;
+                    let MiddlewareResponse { status, headers: resp_headers, body } = response;
 
                     if status.is_success() {
-                        let bytes =
-                            resp.bytes().await.map_err(|e| OpencodeError::Http(Box::new(e)))?;
-                        let parsed: T = serde_json::from_slice(&bytes)?;
+                        let bytes = body.unwrap_or_default();
+                        // `204 No Content` and other empty bodies decode as JSON `null`,
+                        // so callers can ask for `()`.
+                        let parsed: T = if bytes.is_empty() {
+                            serde_json::from_value(serde_json::Value::Null)?
+                        } else {
+                            serde_json::from_slice(&bytes)?
+                        };
                         return Ok(parsed);
                     }
 
-                    // Error response — read body then decide to retry or fail.
-                    let body_bytes = resp.bytes().await.ok();
+                    // Error response — decide to retry or fail.
                     let body_value: Option<serde_json::Value> =
-                        body_bytes.as_ref().and_then(|b| serde_json::from_slice(b).ok());
+                        body.as_ref().and_then(|b| serde_json::from_slice(b).ok());
 
                     let err = OpencodeError::from_response(
                         status.as_u16(),
@@ -334,6 +495,61 @@ impl Opencode {
             .unwrap_or_else(|| OpencodeError::Http("max retries exhausted".to_owned().into())))
     }
 
+    /// Turn a (possibly middleware-rewritten) request into an `hpx` request
+    /// builder.
+    fn send(&self, request: &MiddlewareRequest) -> hpx::RequestBuilder {
+        let mut req = self
+            .http_client
+            .request(request.method.clone(), &request.url)
+            .headers(request.headers.clone());
+        if let Some(ref body) = request.body {
+            req = req.json(body);
+        }
+        req
+    }
+
+    /// Run every middleware's `on_request` hook in registration order.
+    async fn run_request_middleware(
+        &self,
+        request: &mut MiddlewareRequest,
+    ) -> Result<(), OpencodeError> {
+        for middleware in self.middleware.iter() {
+            middleware.on_request(request).await?;
+        }
+        Ok(())
+    }
+
+    /// Run every middleware's `on_response` hook in reverse registration
+    /// order.
+    async fn run_response_middleware(
+        &self,
+        request: &MiddlewareRequest,
+        response: &mut MiddlewareResponse,
+    ) -> Result<(), OpencodeError> {
+        for middleware in self.middleware.iter().rev() {
+            middleware.on_response(request, response).await?;
+        }
+        Ok(())
+    }
+
+    /// Pass a non-retried error response through the middleware, then map it
+    /// to an [`OpencodeError`].
+    async fn error_response(
+        &self,
+        request: &MiddlewareRequest,
+        status: http::StatusCode,
+        headers: HeaderMap,
+        body: Option<bytes::Bytes>,
+    ) -> OpencodeError {
+        let mut response = MiddlewareResponse { status, headers, body };
+        if let Err(err) = self.run_response_middleware(request, &mut response).await {
+            return err;
+        }
+        let body_value: Option<serde_json::Value> =
+            response.body.as_ref().and_then(|b| serde_json::from_slice(b).ok());
+        OpencodeError::from_response(response.status.as_u16(), Some(response.headers), body_value)
+    }
+
     // ── Public convenience methods ─────────────────────────────
 
     /// Send a `GET` request and deserialise the JSON response.
@@ -409,35 +625,176 @@ impl Opencode {
     /// Unlike other HTTP methods, this does NOT parse the full response body.
     /// Instead it returns an [`crate::SseStream`] that lazily decodes each SSE
     /// event's `data` field as JSON of type `T`.
+    ///
+    /// `options` supplies the directory, query and extra headers of the
+    /// connection.
     pub async fn get_stream<T: DeserializeOwned + 'static>(
         &self,
         path: &str,
+        options: Option<&RequestOptions>,
     ) -> Result<crate::streaming::SseStream<T>, OpencodeError> {
-        let url = self.build_url(path, None);
-        let headers = self.build_headers(None, 0);
+        let stream = self.open_stream(path, None, options).await?;
+        Ok(crate::streaming::SseStream::new(stream))
+    }
 
-        let response = self
-            .http_client
-            .get(&url)
-            .headers(headers)
-            .send()
-            .await
-            .map_err(classify_transport_error)?;
+    /// Send a GET request and return the undecoded SSE events.
+    ///
+    /// Like [`get_stream`](Self::get_stream), but yields each
+    /// [`crate::streaming::ServerSentEvent`] as received, with its `event`
+    /// type and `id`, without parsing `data`.
+    pub async fn get_raw_stream(
+        &self,
+        path: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<crate::streaming::RawSseStream, OpencodeError> {
+        let stream = self.open_stream(path, None, options).await?;
+        Ok(crate::streaming::RawSseStream::new(stream))
+    }
+
+    /// Send a GET request and return an SSE stream that reconnects after
+    /// transport errors and EOF.
+    ///
+    /// The initial connection is made before returning, so its errors surface
+    /// here just like with [`get_stream`](Self::get_stream).  Later
+    /// connections resume with `Last-Event-ID` and back off according to
+    /// `reconnect`; they reuse `options`, and cancelling
+    /// [`RequestOptions::cancel`] abandons a pending reconnection.
+    pub async fn get_stream_reconnecting<T: DeserializeOwned + 'static>(
+        &self,
+        path: &str,
+        reconnect: crate::streaming::ReconnectOptions,
+        options: Option<&RequestOptions>,
+    ) -> Result<crate::streaming::ReconnectingSseStream<T>, OpencodeError> {
+        let stream = self.open_stream(path, None, options).await?;
+        let cancel = options.and_then(|o| o.cancel.clone());
+        let client = self.clone();
+        let path = path.to_owned();
+        let options = options.cloned();
+        let connector: crate::streaming::Connector = Arc::new(move |last_event_id| {
+            let client = client.clone();
+            let path = path.clone();
+            let options = options.clone();
+            Box::pin(async move {
+                client.open_stream(&path, last_event_id.as_deref(), options.as_ref()).await
+            })
+        });
+        Ok(crate::streaming::ReconnectingSseStream::new(stream, connector, reconnect, cancel))
+    }
+
+    /// Open an SSE connection and return its body, sending `Last-Event-ID`
+    /// when resuming.  Connecting is abandoned if [`RequestOptions::cancel`]
+    /// fires.
+    async fn open_stream(
+        &self,
+        path: &str,
+        last_event_id: Option<&str>,
+        options: Option<&RequestOptions>,
+    ) -> Result<crate::streaming::ByteStream, OpencodeError> {
+        let connect = self.connect_stream(path, last_event_id, options);
+        match options.and_then(|o| o.cancel.as_ref()) {
+            Some(token) => token.run(connect).await?,
+            None => connect.await,
+        }
+    }
+
+    /// The connection handshake behind [`open_stream`](Self::open_stream).
+    async fn connect_stream(
+        &self,
+        path: &str,
+        last_event_id: Option<&str>,
+        options: Option<&RequestOptions>,
+    ) -> Result<crate::streaming::ByteStream, OpencodeError> {
+        let mut headers = self.build_headers(options.and_then(|o| o.extra_headers.as_ref()), 0);
+        if let Some(id) = last_event_id.and_then(|id| HeaderValue::from_str(id).ok()) {
+            headers.insert("last-event-id", id);
+        }
+        let mut request = MiddlewareRequest {
+            method: http::Method::GET,
+            url: self.build_url(path, &QueryPairs::default(), options),
+            headers,
+            body: None,
+            attempt: 0,
+        };
+        self.run_request_middleware(&mut request).await?;
+
+        let response = self.send(&request).send().await.map_err(classify_transport_error)?;
 
         let status = response.status();
+        let headers = response.headers().clone();
         if !status.is_success() {
-            let resp_headers = response.headers().clone();
-            let body_bytes = response.bytes().await.ok();
+            let body = response.bytes().await.ok();
+            return Err(self.error_response(&request, status, headers, body).await);
+        }
+
+        let mut head = MiddlewareResponse { status, headers, body: None };
+        self.run_response_middleware(&request, &mut head).await?;
+        if !head.status.is_success() {
             let body_value: Option<serde_json::Value> =
-                body_bytes.as_ref().and_then(|b| serde_json::from_slice(b).ok());
+                head.body.as_ref().and_then(|b| serde_json::from_slice(b).ok());
             return Err(OpencodeError::from_response(
-                status.as_u16(),
-                Some(resp_headers),
+                head.status.as_u16(),
+                Some(head.headers),
                 body_value,
             ));
         }
 
-        Ok(crate::streaming::SseStream::new(response.bytes_stream()))
+        Ok(Box::pin(response.bytes_stream()))
+    }
+
+    /// Open a WebSocket connection via an HTTP/1.1 `GET` upgrade.
+    ///
+    /// The handshake honours the per-request timeout and extra headers but is
+    /// never retried.  A non-`101` response is mapped like any other error
+    /// response, and the server's `Sec-WebSocket-Accept` is verified before
+    /// the connection is split into its reader and writer halves.
+    pub(crate) async fn connect_websocket(
+        &self,
+        path: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<(WebSocketReader, WebSocketWriter), OpencodeError> {
+        let timeout = options.and_then(|o| o.timeout).unwrap_or(self.timeout);
+        let mut request = MiddlewareRequest {
+            method: http::Method::GET,
+            url: self.build_url(path, &QueryPairs::default(), options),
+            headers: self.build_headers(options.and_then(|o| o.extra_headers.as_ref()), 0),
+            body: None,
+            attempt: 0,
+        };
+        self.run_request_middleware(&mut request).await?;
+
+        tracing::debug!(url = %request.url, "opening websocket");
+
+        // Pongs are answered by our own writer task; hpx's split reader
+        // cannot write them.
+        let handshake = tokio::time::timeout(
+            timeout,
+            hpx::ws::WebSocketRequestBuilder::new(
+                self.send(&request).version(http::Version::HTTP_11),
+            )
+            .auto_pong(false)
+            .send(),
+        );
+        let handshake = match options.and_then(|o| o.cancel.as_ref()) {
+            Some(token) => token.run(handshake).await?,
+            None => handshake.await,
+        };
+        let mut response =
+            handshake.map_err(|_| OpencodeError::Timeout)?.map_err(classify_transport_error)?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        if status != http::StatusCode::SWITCHING_PROTOCOLS {
+            let failed =
+                std::mem::replace(&mut *response, http::Response::new(hpx::Body::default()).into());
+            let body = failed.bytes().await.ok();
+            return Err(self.error_response(&request, status, headers, body).await);
+        }
+
+        let mut head = MiddlewareResponse { status, headers, body: None };
+        self.run_response_middleware(&request, &mut head).await?;
+
+        let socket = response.into_websocket().await.map_err(classify_transport_error)?;
+        Ok(crate::websocket::split(socket))
     }
 
     /// Send a `DELETE` request with an optional JSON body.
@@ -500,7 +857,7 @@ fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
 }
 
 /// Generate a jitter factor in `[0.75, 1.0)` using system-clock entropy.
-fn jitter_factor() -> f64 {
+pub(crate) fn jitter_factor() -> f64 {
     let nanos = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
@@ -520,9 +877,18 @@ fn classify_transport_error(err: hpx::Error) -> OpencodeError {
 }
 
 /// Fluent builder for [`Opencode`].
-#[derive(Debug)]
 pub struct OpencodeBuilder {
     options: ClientOptions,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl std::fmt::Debug for OpencodeBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpencodeBuilder")
+            .field("options", &self.options)
+            .field("middleware", &self.middleware.len())
+            .finish()
+    }
 }
 
 impl OpencodeBuilder {
@@ -561,6 +927,15 @@ impl OpencodeBuilder {
         self
     }
 
+    /// Register a [`Middleware`].
+    ///
+    /// Request hooks run in registration order, response hooks in reverse.
+    #[must_use]
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
     /// Build the [`Opencode`] client.
     ///
     /// # Errors
@@ -568,7 +943,9 @@ impl OpencodeBuilder {
     /// Returns [`OpencodeError::Http`] if the underlying HTTP client cannot be
     /// built.
     pub fn build(self) -> Result<Opencode, OpencodeError> {
-        Opencode::with_options(&self.options)
+        let mut client = Opencode::with_options(&self.options)?;
+        client.middleware = self.middleware.into();
+        Ok(client)
     }
 }
 
@@ -659,7 +1036,7 @@ mod tests {
     #[test]
     fn build_url_simple_path() {
         let client = test_client();
-        let url = client.build_url("/app", None);
+        let url = client.build_url("/app", &QueryPairs::default(), None);
         assert_eq!(url, format!("{DEFAULT_BASE_URL}/app"));
     }
 
@@ -667,13 +1044,16 @@ mod tests {
     fn build_url_strips_trailing_slash_from_base() {
         let client =
             test_client_with_defaults("http://example.com/", HashMap::new(), HeaderMap::new());
-        assert_eq!(client.build_url("/path", None), "http://example.com/path");
+        assert_eq!(
+            client.build_url("/path", &QueryPairs::default(), None),
+            "http://example.com/path"
+        );
     }
 
     #[test]
     fn build_url_adds_leading_slash() {
         let client = test_client();
-        let url = client.build_url("session", None);
+        let url = client.build_url("session", &QueryPairs::default(), None);
         assert_eq!(url, format!("{DEFAULT_BASE_URL}/session"));
     }
 
@@ -683,7 +1063,7 @@ mod tests {
         dq.insert("version".to_owned(), "2".to_owned());
         let client = test_client_with_defaults("http://host", dq, HeaderMap::new());
 
-        let url = client.build_url("/api", None);
+        let url = client.build_url("/api", &QueryPairs::default(), None);
         assert_eq!(url, "http://host/api?version=2");
     }
 
@@ -691,10 +1071,9 @@ mod tests {
     fn build_url_with_extra_query() {
         let client = test_client_with_defaults("http://host", HashMap::new(), HeaderMap::new());
 
-        let mut extra = HashMap::new();
-        extra.insert("foo".to_owned(), "bar".to_owned());
+        let extra: QueryPairs = std::iter::once(("foo".to_owned(), "bar".to_owned())).collect();
 
-        let url = client.build_url("/api", Some(&extra));
+        let url = client.build_url("/api", &extra, None);
         assert_eq!(url, "http://host/api?foo=bar");
     }
 
@@ -705,18 +1084,76 @@ mod tests {
 
         let client = test_client_with_defaults("http://host", dq, HeaderMap::new());
 
-        let mut extra = HashMap::new();
-        extra.insert("b".to_owned(), "2".to_owned());
+        let extra: QueryPairs = std::iter::once(("b".to_owned(), "2".to_owned())).collect();
 
-        let url = client.build_url("/x", Some(&extra));
+        let url = client.build_url("/x", &extra, None);
         // Sorted by key
         assert_eq!(url, "http://host/x?a=1&b=2");
     }
 
+    #[test]
+    fn build_url_encodes_query_values() {
+        let mut dq = HashMap::new();
+        dq.insert("directory".to_owned(), "/home/me/my repo".to_owned());
+        let client = test_client_with_defaults("http://host", dq, HeaderMap::new());
+
+        let extra: QueryPairs = std::iter::once(("q".to_owned(), "a&b#c".to_owned())).collect();
+        let url = client.build_url("/find", &extra, None);
+        assert_eq!(url, "http://host/find?directory=%2Fhome%2Fme%2Fmy%20repo&q=a%26b%23c");
+    }
+
+    #[test]
+    fn build_url_request_options_override_query() {
+        let mut dq = HashMap::new();
+        dq.insert("directory".to_owned(), "/default".to_owned());
+        dq.insert("keep".to_owned(), "1".to_owned());
+        let client = test_client_with_defaults("http://host", dq, HeaderMap::new());
+
+        let extra: QueryPairs =
+            std::iter::once(("messageID".to_owned(), "msg_1".to_owned())).collect();
+        let opts = RequestOptions {
+            query: Some(vec![
+                ("directory".to_owned(), "/other".to_owned()),
+                ("tag".to_owned(), "x".to_owned()),
+                ("tag".to_owned(), "y".to_owned()),
+            ]),
+            ..RequestOptions::default()
+        };
+        let url = client.build_url("/x", &extra, Some(&opts));
+        assert_eq!(url, "http://host/x?keep=1&messageID=msg_1&directory=%2Fother&tag=x&tag=y");
+    }
+
+    #[test]
+    fn scoped_client_adds_directory() {
+        let mut dq = HashMap::new();
+        dq.insert("directory".to_owned(), "/default".to_owned());
+        let client = test_client_with_defaults("http://host", dq, HeaderMap::new());
+        let scoped = client.scoped("/work/repo a");
+
+        assert_eq!(client.directory(), None);
+        assert_eq!(scoped.directory(), Some("/work/repo a"));
+        assert_eq!(
+            scoped.build_url("/session", &QueryPairs::default(), None),
+            "http://host/session?directory=%2Fwork%2Frepo%20a"
+        );
+    }
+
+    #[test]
+    fn request_options_directory_overrides_scope() {
+        let client = test_client_with_defaults("http://host", HashMap::new(), HeaderMap::new())
+            .scoped("/scoped");
+        let opts =
+            RequestOptions { directory: Some("/per-call".to_owned()), ..RequestOptions::default() };
+        assert_eq!(
+            client.build_url("/session", &QueryPairs::default(), Some(&opts)),
+            "http://host/session?directory=%2Fper-call"
+        );
+    }
+
     #[test]
     fn build_url_no_query_no_question_mark() {
         let client = test_client();
-        let url = client.build_url("/clean", None);
+        let url = client.build_url("/clean", &QueryPairs::default(), None);
         assert!(!url.contains('?'));
     }
 
@@ -878,5 +1315,9 @@ mod tests {
         assert!(opts.extra_headers.is_none());
         assert!(opts.timeout.is_none());
         assert!(opts.max_retries.is_none());
+        assert!(opts.query.is_none());
+        assert!(opts.directory.is_none());
+        assert!(opts.cancel.is_none());
+        assert!(!opts.abort_on_cancel);
     }
 }
@@ -0,0 +1,379 @@
+//! Typed dispatch of `/event` stream events to async handlers.
+//!
+//! Instead of matching on every [`EventListResponse`] variant, register a
+//! handler per event type on an [`EventRouter`] and [`run`](EventRouter::run)
+//! it.  The router reads [`EventResource::list_reconnecting`], optionally
+//! keeps only events for the sessions it was given, and runs matching
+//! handlers concurrently on the Tokio runtime.
+//!
+//! ```rust,no_run
+//! use opencode_sdk_rs::{CancellationToken, EventRouter, Opencode};
+//!
+//! # async fn example() -> Result<(), opencode_sdk_rs::OpencodeError> {
+//! let client = Opencode::new()?;
+//! let shutdown = CancellationToken::new();
+//!
+//! let router = EventRouter::new()
+//!     .session("ses_123")
+//!     .on_session_idle(|props| async move {
+//!         println!("{} is idle", props.session_id);
+//!     })
+//!     .on_permission_asked(|request| async move {
+//!         println!("permission asked: {}", request.permission);
+//!     });
+//!
+//! // Call `shutdown.cancel()` elsewhere to stop; in-flight handlers finish first.
+//! router.run(&client, shutdown).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`EventResource::list_reconnecting`]: crate::resources::event::EventResource::list_reconnecting
+
+use std::{collections::HashSet, future::Future, pin::Pin, sync::Arc};
+
+use futures_core::Stream;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    cancellation::CancellationToken,
+    client::Opencode,
+    error::OpencodeError,
+    middleware::BoxFuture,
+    resources::{
+        event::{
+            CommandExecutedProps, EmptyProps, EventListResponse, FileEditedProps,
+            FileWatcherUpdatedProps, InstallationUpdateAvailableProps, InstallationUpdatedProps,
+            LspClientDiagnosticsProps, McpBrowserOpenFailedProps, McpToolsChangedProps,
+            MessagePartDeltaProps, MessagePartRemovedProps, MessagePartUpdatedProps,
+            MessageRemovedProps, MessageUpdatedProps, PermissionRepliedProps, PtyCreatedProps,
+            PtyDeletedProps, PtyExitedProps, PtyUpdatedProps, QuestionRejectedProps,
+            QuestionRepliedProps, ServerInstanceDisposedProps, SessionCompactedProps,
+            SessionCreatedProps, SessionDeletedProps, SessionDiffProps, SessionErrorProps,
+            SessionIdleProps, SessionStatusProps, SessionUpdatedProps, TodoUpdatedProps,
+            TuiCommandExecuteProps, TuiPromptAppendProps, TuiSessionSelectProps, TuiToastShowProps,
+            VcsBranchUpdatedProps, WorktreeFailedProps, WorktreeReadyProps,
+        },
+        permission::PermissionRequest,
+        project::Project,
+        question::QuestionRequest,
+    },
+    streaming::{ReconnectOptions, SseItem},
+};
+
+/// Handlers allowed to run at once unless changed with
+/// [`EventRouter::max_concurrency`].
+const DEFAULT_MAX_CONCURRENCY: usize = 32;
+
+/// A registered handler: returns the work to run if it wants `event`.
+type Route = Arc<dyn Fn(&EventListResponse) -> Option<BoxFuture<'static, ()>> + Send + Sync>;
+
+/// A handler for [`SseItem::Reconnected`].
+type ReconnectHandler = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Dispatches events from the `/event` stream to per-type async handlers.
+///
+/// Every handler registered for an event's type runs, each as its own Tokio
+/// task; at most [`max_concurrency`](Self::max_concurrency) run at once, and
+/// reading the stream pauses while that many are busy.  With a limit of `1`
+/// handlers run one at a time in stream order.
+///
+/// The stream reconnects after dropped connections (see
+/// [`reconnect`](Self::reconnect)); events sent while disconnected may be
+/// missed, so use [`on_reconnected`](Self::on_reconnected) to resync.
+pub struct EventRouter {
+    routes: Vec<Route>,
+    reconnected: Vec<ReconnectHandler>,
+    sessions: HashSet<String>,
+    max_concurrency: usize,
+    reconnect: ReconnectOptions,
+}
+
+impl std::fmt::Debug for EventRouter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventRouter")
+            .field("routes", &self.routes.len())
+            .field("reconnected", &self.reconnected.len())
+            .field("sessions", &self.sessions)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("reconnect", &self.reconnect)
+            .finish()
+    }
+}
+
+impl Default for EventRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventRouter {
+    /// Create a router with no handlers that accepts events for every
+    /// session.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            reconnected: Vec::new(),
+            sessions: HashSet::new(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            reconnect: ReconnectOptions::default(),
+        }
+    }
+
+    /// Only dispatch session events for `session_id`.
+    ///
+    /// May be called repeatedly to accept several sessions.  Events that are
+    /// not tied to a session (see [`EventListResponse::session_id`]) are
+    /// always dispatched.
+    #[must_use]
+    pub fn session(mut self, session_id: impl Into<String>) -> Self {
+        self.sessions.insert(session_id.into());
+        self
+    }
+
+    /// Limit how many handlers may run at once (at least one).
+    #[must_use]
+    pub fn max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
+    /// Back-off settings for reconnecting the event stream.
+    #[must_use]
+    pub const fn reconnect(mut self, options: ReconnectOptions) -> Self {
+        self.reconnect = options;
+        self
+    }
+
+    /// Run `handler` each time the event stream has reconnected, e.g. to
+    /// refresh state that missed events may have left stale.
+    #[must_use]
+    pub fn on_reconnected<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.reconnected.push(Arc::new(move || Box::pin(handler())));
+        self
+    }
+
+    /// Handle every event, including [`EventListResponse::Unknown`] ones.
+    #[must_use]
+    pub fn on_event<F, Fut>(self, handler: F) -> Self
+    where
+        F: Fn(EventListResponse) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.route(move |event| Some(Box::pin(handler(event.clone()))))
+    }
+
+    fn route(
+        mut self,
+        route: impl Fn(&EventListResponse) -> Option<BoxFuture<'static, ()>> + Send + Sync + 'static,
+    ) -> Self {
+        self.routes.push(Arc::new(route));
+        self
+    }
+
+    /// Whether `event` passes the session filter.
+    fn accepts(&self, event: &EventListResponse) -> bool {
+        self.sessions.is_empty() || event.session_id().is_none_or(|id| self.sessions.contains(id))
+    }
+
+    /// Subscribe to [`EventResource::list_reconnecting`] and dispatch events
+    /// until reconnection is given up or `shutdown` is cancelled.
+    ///
+    /// In either case the router stops reading and waits for handlers
+    /// already running to finish before returning, so cancelling `shutdown`
+    /// is a graceful stop.  The error that ended reconnection is returned
+    /// after that.  An event that cannot be decoded is logged and skipped,
+    /// and a handler that panics is logged; neither stops the router.
+    ///
+    /// [`EventResource::list_reconnecting`]: crate::resources::event::EventResource::list_reconnecting
+    pub async fn run(
+        &self,
+        client: &Opencode,
+        shutdown: CancellationToken,
+    ) -> Result<(), OpencodeError> {
+        let events = client.event();
+        let subscribe = events.list_reconnecting(self.reconnect.clone(), None);
+        let Ok(stream) = shutdown.run(subscribe).await else {
+            return Ok(());
+        };
+        let mut stream = stream?;
+
+        let permits = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut tasks = JoinSet::new();
+        let mut result = Ok(());
+        loop {
+            let next = std::future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx));
+            let work: Vec<_> = match shutdown.run(next).await {
+                Ok(Some(Ok(SseItem::Event(event)))) if self.accepts(&event) => {
+                    self.routes.iter().filter_map(|route| route(&event)).collect()
+                }
+                Ok(Some(Ok(SseItem::Event(_)))) => continue,
+                Ok(Some(Ok(SseItem::Reconnected))) => {
+                    self.reconnected.iter().map(|handler| handler()).collect()
+                }
+                Ok(Some(Err(OpencodeError::Serialization(err)))) => {
+                    tracing::warn!(error = %err, "skipping undecodable event");
+                    continue;
+                }
+                Ok(Some(Err(err))) => {
+                    result = Err(err);
+                    break;
+                }
+                Ok(None) | Err(_) => break,
+            };
+
+            // An item that was read is dispatched in full, even if shutdown
+            // is requested meanwhile.
+            for work in work {
+                let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+                    break;
+                };
+                tasks.spawn(async move {
+                    work.await;
+                    drop(permit);
+                });
+            }
+            while let Some(joined) = tasks.try_join_next() {
+                log_panic(joined);
+            }
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            log_panic(joined);
+        }
+        result
+    }
+}
+
+fn log_panic(joined: Result<(), tokio::task::JoinError>) {
+    if let Err(err) = joined {
+        tracing::warn!(error = %err, "event handler failed");
+    }
+}
+
+/// Generate one `on_*` registration method per typed event variant.
+macro_rules! handlers {
+    ($($method:ident => $variant:ident($props:ty);)*) => {
+        impl EventRouter {
+            $(
+                #[doc = concat!("Handle [`EventListResponse::", stringify!($variant), "`] events.")]
+                #[must_use]
+                pub fn $method<F, Fut>(self, handler: F) -> Self
+                where
+                    F: Fn($props) -> Fut + Send + Sync + 'static,
+                    Fut: Future<Output = ()> + Send + 'static,
+                {
+                    self.route(move |event| match event {
+                        EventListResponse::$variant { properties } => {
+                            Some(Box::pin(handler(properties.clone())))
+                        }
+                        _ => None,
+                    })
+                }
+            )*
+        }
+    };
+}
+
+handlers! {
+    on_installation_updated => InstallationUpdated(InstallationUpdatedProps);
+    on_installation_update_available => InstallationUpdateAvailable(InstallationUpdateAvailableProps);
+    on_project_updated => ProjectUpdated(Project);
+    on_server_instance_disposed => ServerInstanceDisposed(ServerInstanceDisposedProps);
+    on_server_connected => ServerConnected(EmptyProps);
+    on_global_disposed => GlobalDisposed(EmptyProps);
+    on_lsp_client_diagnostics => LspClientDiagnostics(LspClientDiagnosticsProps);
+    on_lsp_updated => LspUpdated(EmptyProps);
+    on_file_edited => FileEdited(FileEditedProps);
+    on_file_watcher_updated => FileWatcherUpdated(FileWatcherUpdatedProps);
+    on_message_updated => MessageUpdated(MessageUpdatedProps);
+    on_message_removed => MessageRemoved(MessageRemovedProps);
+    on_message_part_updated => MessagePartUpdated(MessagePartUpdatedProps);
+    on_message_part_delta => MessagePartDelta(MessagePartDeltaProps);
+    on_message_part_removed => MessagePartRemoved(MessagePartRemovedProps);
+    on_permission_asked => PermissionAsked(PermissionRequest);
+    on_permission_replied => PermissionReplied(PermissionRepliedProps);
+    on_session_created => SessionCreated(SessionCreatedProps);
+    on_session_updated => SessionUpdated(SessionUpdatedProps);
+    on_session_deleted => SessionDeleted(SessionDeletedProps);
+    on_session_status => SessionStatus(SessionStatusProps);
+    on_session_idle => SessionIdle(SessionIdleProps);
+    on_session_diff => SessionDiff(SessionDiffProps);
+    on_session_compacted => SessionCompacted(SessionCompactedProps);
+    on_session_error => SessionError(SessionErrorProps);
+    on_question_asked => QuestionAsked(QuestionRequest);
+    on_question_replied => QuestionReplied(QuestionRepliedProps);
+    on_question_rejected => QuestionRejected(QuestionRejectedProps);
+    on_todo_updated => TodoUpdated(TodoUpdatedProps);
+    on_tui_prompt_append => TuiPromptAppend(TuiPromptAppendProps);
+    on_tui_command_execute => TuiCommandExecute(TuiCommandExecuteProps);
+    on_tui_toast_show => TuiToastShow(TuiToastShowProps);
+    on_tui_session_select => TuiSessionSelect(TuiSessionSelectProps);
+    on_mcp_tools_changed => McpToolsChanged(McpToolsChangedProps);
+    on_mcp_browser_open_failed => McpBrowserOpenFailed(McpBrowserOpenFailedProps);
+    on_command_executed => CommandExecuted(CommandExecutedProps);
+    on_vcs_branch_updated => VcsBranchUpdated(VcsBranchUpdatedProps);
+    on_pty_created => PtyCreated(PtyCreatedProps);
+    on_pty_updated => PtyUpdated(PtyUpdatedProps);
+    on_pty_exited => PtyExited(PtyExitedProps);
+    on_pty_deleted => PtyDeleted(PtyDeletedProps);
+    on_worktree_ready => WorktreeReady(WorktreeReadyProps);
+    on_worktree_failed => WorktreeFailed(WorktreeFailedProps);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn idle(session_id: &str) -> EventListResponse {
+        EventListResponse::SessionIdle {
+            properties: SessionIdleProps { session_id: session_id.into() },
+        }
+    }
+
+    #[test]
+    fn routes_match_only_their_variant() {
+        let router = EventRouter::new()
+            .on_session_idle(|_| async {})
+            .on_permission_asked(|_| async {})
+            .on_event(|_| async {});
+        let matched = router.routes.iter().filter(|route| route(&idle("s1")).is_some()).count();
+        assert_eq!(matched, 2);
+    }
+
+    #[test]
+    fn session_filter_keeps_global_events() {
+        let router = EventRouter::new().session("s1").session("s2");
+        assert!(router.accepts(&idle("s1")));
+        assert!(router.accepts(&idle("s2")));
+        assert!(!router.accepts(&idle("s3")));
+        let global = EventListResponse::ServerConnected { properties: EmptyProps {} };
+        assert!(router.accepts(&global));
+        assert!(EventRouter::new().accepts(&idle("s3")));
+    }
+
+    #[tokio::test]
+    async fn handlers_receive_properties() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let router = EventRouter::new().on_session_idle({
+            let seen = Arc::clone(&seen);
+            move |props| {
+                let seen = Arc::clone(&seen);
+                async move { seen.lock().unwrap().push(props.session_id) }
+            }
+        });
+        for route in &router.routes {
+            if let Some(work) = route(&idle("s1")) {
+                work.await;
+            }
+        }
+        assert_eq!(*seen.lock().unwrap(), vec!["s1".to_owned()]);
+    }
+}
@@ -25,15 +25,23 @@
 //! }
 //! ```
 
+pub mod cancellation;
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod event_router;
+pub mod middleware;
 pub mod resources;
 pub mod streaming;
 pub mod types;
+mod url;
+mod websocket;
 
 // Re-export key types at the crate root for convenience
+pub use cancellation::CancellationToken;
 pub use client::{Opencode, OpencodeBuilder, RequestOptions};
 pub use config::ClientOptions;
 pub use error::OpencodeError;
-pub use streaming::SseStream;
+pub use event_router::EventRouter;
+pub use middleware::Middleware;
+pub use streaming::{RawSseStream, SseStream};
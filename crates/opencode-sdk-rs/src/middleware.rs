@@ -0,0 +1,109 @@
+//! Request/response middleware.
+//!
+//! A [`Middleware`] registered through [`crate::OpencodeBuilder::middleware`]
+//! sees every HTTP request the client sends — the JSON methods, event streams
+//! and WebSocket handshakes alike.  Typical uses are token refresh, request
+//! signing, logging and header rewriting.
+//!
+//! Hooks run in an onion order: [`Middleware::on_request`] in registration
+//! order just before each attempt is sent (so retries are seen too), and
+//! [`Middleware::on_response`] in reverse registration order once the
+//! response arrives.  Returning an error from either hook fails the request
+//! with that error; it is not retried.
+//!
+//! ```rust,no_run
+//! use opencode_sdk_rs::{
+//!     Opencode, OpencodeError,
+//!     middleware::{BoxFuture, Middleware, MiddlewareRequest},
+//! };
+//!
+//! struct BearerToken(String);
+//!
+//! impl Middleware for BearerToken {
+//!     fn on_request<'a>(
+//!         &'a self,
+//!         request: &'a mut MiddlewareRequest,
+//!     ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+//!         Box::pin(async move {
+//!             let value = format!("Bearer {}", self.0)
+//!                 .parse()
+//!                 .map_err(|_| OpencodeError::Http("token is not a valid header value".into()))?;
+//!             request.headers.insert(http::header::AUTHORIZATION, value);
+//!             Ok(())
+//!         })
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), OpencodeError> {
+//! let _client = Opencode::builder().middleware(BearerToken("secret".to_owned())).build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{future::Future, pin::Pin};
+
+use bytes::Bytes;
+use http::{HeaderMap, Method, StatusCode};
+
+use crate::error::OpencodeError;
+
+/// A boxed, `Send` future returned by [`Middleware`] hooks.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// An outgoing request as seen by [`Middleware::on_request`].
+///
+/// Every field may be rewritten; the client sends whatever is left after the
+/// last hook has run.
+#[derive(Debug, Clone)]
+pub struct MiddlewareRequest {
+    /// HTTP method.
+    pub method: Method,
+    /// Fully built URL, including the percent-encoded query string.
+    pub url: String,
+    /// Request headers, including defaults and per-request extras.
+    pub headers: HeaderMap,
+    /// JSON body, if any.
+    pub body: Option<serde_json::Value>,
+    /// Zero-based attempt number; greater than zero on retries.
+    pub attempt: u32,
+}
+
+/// A received response as seen by [`Middleware::on_response`].
+///
+/// Changes made here are what the client goes on to interpret, so a hook can
+/// rewrite the status, headers or body before error mapping and decoding.
+#[derive(Debug, Clone)]
+pub struct MiddlewareResponse {
+    /// HTTP status code.
+    pub status: StatusCode,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// The full response body.  `None` for successful event streams and
+    /// WebSocket upgrades, whose bodies are consumed incrementally.
+    pub body: Option<Bytes>,
+}
+
+/// Hooks invoked around every HTTP request the client sends.
+///
+/// Both hooks default to doing nothing, so implementors only override the
+/// ones they need.
+pub trait Middleware: Send + Sync + 'static {
+    /// Inspect or rewrite a request just before it is sent.
+    fn on_request<'a>(
+        &'a self,
+        request: &'a mut MiddlewareRequest,
+    ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+        let _ = request;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Inspect or rewrite a response before the client interprets it.
+    fn on_response<'a>(
+        &'a self,
+        request: &'a MiddlewareRequest,
+        response: &'a mut MiddlewareResponse,
+    ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+        let _ = (request, response);
+        Box::pin(async { Ok(()) })
+    }
+}
@@ -0,0 +1,145 @@
+//! Agent resource types and methods mirroring the JS SDK's `resources/agent.ts`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{app::ModeModel, session::PermissionRuleset};
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Where an agent can be used.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentMode {
+    /// Only invocable as a subagent (e.g. via `@name` or the task tool).
+    Subagent,
+    /// Selectable as the session's primary agent.
+    Primary,
+    /// Usable both as a primary agent and as a subagent.
+    All,
+}
+
+/// A resolved agent as reported by the server.
+///
+/// Unlike [`Agent`](super::config::Agent), which is the raw configuration map,
+/// this includes built-in agents and the effective permission rules.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentInfo {
+    /// Agent name, as passed in
+    /// [`SessionChatParams::agent`](super::session::SessionChatParams::agent).
+    pub name: String,
+    /// Human-readable description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Where the agent can be used.
+    pub mode: AgentMode,
+    /// Whether the agent is built into `OpenCode`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native: Option<bool>,
+    /// Whether the agent is hidden from agent pickers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    /// Nucleus sampling override.
+    #[serde(rename = "topP", skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Temperature override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Display colour.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Effective permission rules.
+    pub permission: PermissionRuleset,
+    /// Model override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModeModel>,
+    /// Model variant override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// System prompt override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Arbitrary provider-specific options.
+    pub options: HashMap<String, serde_json::Value>,
+    /// Maximum number of agentic steps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<u64>,
+}
+
+/// Response type for [`AgentResource::list`].
+pub type AgentListResponse = Vec<AgentInfo>;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/agent` endpoint.
+pub struct AgentResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> AgentResource<'a> {
+    /// Create a new `AgentResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List all available agents (`GET /agent`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<AgentListResponse, OpencodeError> {
+        self.client.get("/agent", options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn agent_info_full_round_trip() {
+        let raw = json!({
+            "name": "review",
+            "description": "Reviews diffs without editing files",
+            "mode": "subagent",
+            "native": false,
+            "topP": 0.9,
+            "temperature": 0.1,
+            "color": "#00aaff",
+            "permission": [{ "permission": "edit", "pattern": "*", "action": "deny" }],
+            "model": { "modelID": "claude-sonnet-4", "providerID": "anthropic" },
+            "prompt": "You are a careful reviewer.",
+            "options": {},
+            "steps": 20
+        });
+        let agent: AgentInfo = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(agent.mode, AgentMode::Subagent);
+        assert_eq!(agent.top_p, Some(0.9));
+        assert_eq!(agent.permission[0].action, "deny");
+        assert_eq!(agent.model.as_ref().map(|m| m.provider_id.as_str()), Some("anthropic"));
+        assert_eq!(serde_json::to_value(&agent).unwrap(), raw);
+    }
+
+    #[test]
+    fn agent_info_minimal() {
+        let raw = r#"{"name":"build","mode":"primary","permission":[],"options":{}}"#;
+        let agent: AgentInfo = serde_json::from_str(raw).unwrap();
+        assert_eq!(agent.mode, AgentMode::Primary);
+        assert!(agent.description.is_none());
+        assert!(agent.model.is_none());
+    }
+}
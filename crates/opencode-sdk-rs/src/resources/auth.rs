@@ -0,0 +1,208 @@
+//! Auth resource types and methods mirroring the JS SDK's `resources/auth.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// OAuth credentials for a provider.
+///
+/// The `Debug` output redacts both tokens.
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+pub struct OAuth {
+    /// Refresh token.
+    pub refresh: String,
+    /// Access token.
+    pub access: String,
+    /// Access token expiry (Unix epoch milliseconds).
+    pub expires: f64,
+    /// Provider account identifier.
+    #[serde(rename = "accountId", skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    /// Enterprise deployment URL (e.g. GitHub Enterprise).
+    #[serde(rename = "enterpriseUrl", skip_serializing_if = "Option::is_none")]
+    pub enterprise_url: Option<String>,
+}
+
+/// A plain API key.
+///
+/// The `Debug` output redacts the key.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiAuth {
+    /// The API key.
+    pub key: String,
+}
+
+/// A token issued through a provider's `.well-known/opencode` endpoint.
+///
+/// The `Debug` output redacts the token.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WellKnownAuth {
+    /// Environment variable name the token is exposed as.
+    pub key: String,
+    /// The token value.
+    pub token: String,
+}
+
+impl std::fmt::Debug for OAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuth")
+            .field("refresh", &REDACTED)
+            .field("access", &REDACTED)
+            .field("expires", &self.expires)
+            .field("account_id", &self.account_id)
+            .field("enterprise_url", &self.enterprise_url)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for ApiAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiAuth").field("key", &REDACTED).finish()
+    }
+}
+
+impl std::fmt::Debug for WellKnownAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WellKnownAuth").field("key", &self.key).field("token", &REDACTED).finish()
+    }
+}
+
+/// Placeholder printed instead of secrets.
+const REDACTED: &str = "<redacted>";
+
+/// Credentials stored for a provider, discriminated by `type`.
+///
+/// The `Debug` output never includes the secret values.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum AuthInfo {
+    /// OAuth refresh/access token pair.
+    #[serde(rename = "oauth")]
+    OAuth(OAuth),
+    /// API key.
+    #[serde(rename = "api")]
+    Api(ApiAuth),
+    /// Well-known token.
+    #[serde(rename = "wellknown")]
+    WellKnown(WellKnownAuth),
+}
+
+impl AuthInfo {
+    /// Convenience constructor for [`AuthInfo::Api`].
+    pub fn api_key(key: impl Into<String>) -> Self {
+        Self::Api(ApiAuth { key: key.into() })
+    }
+}
+
+/// Response type for [`AuthResource::set`].
+pub type AuthSetResponse = bool;
+
+/// Response type for [`AuthResource::remove`].
+pub type AuthRemoveResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/auth` endpoints.
+pub struct AuthResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> AuthResource<'a> {
+    /// Create a new `AuthResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Set credentials for a provider (`PUT /auth/{providerID}`).
+    pub async fn set(
+        &self,
+        provider_id: &str,
+        auth: &AuthInfo,
+        options: Option<&RequestOptions>,
+    ) -> Result<AuthSetResponse, OpencodeError> {
+        self.client.put(&encode_path!("/auth/{}", provider_id), Some(auth), options).await
+    }
+
+    /// Remove credentials for a provider (`DELETE /auth/{providerID}`).
+    pub async fn remove(
+        &self,
+        provider_id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<AuthRemoveResponse, OpencodeError> {
+        self.client.delete::<bool, ()>(&encode_path!("/auth/{}", provider_id), None, options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn auth_info_api_serialize() {
+        let v = serde_json::to_value(AuthInfo::api_key("sk-test")).unwrap();
+        assert_eq!(v, json!({ "type": "api", "key": "sk-test" }));
+    }
+
+    #[test]
+    fn auth_info_debug_redacts_secrets() {
+        let oauth = AuthInfo::OAuth(OAuth {
+            refresh: "r-token".into(),
+            access: "a-token".into(),
+            expires: 0.0,
+            account_id: Some("acct_1".into()),
+            enterprise_url: None,
+        });
+        let wellknown =
+            AuthInfo::WellKnown(WellKnownAuth { key: "CORP_TOKEN".into(), token: "t-123".into() });
+        let printed = format!("{oauth:?} {:?} {wellknown:?}", AuthInfo::api_key("sk-test"));
+        for secret in ["r-token", "a-token", "sk-test", "t-123"] {
+            assert!(!printed.contains(secret), "{secret} leaked in {printed}");
+        }
+        assert!(printed.contains("acct_1"));
+        assert!(printed.contains("CORP_TOKEN"));
+    }
+
+    #[test]
+    fn auth_info_oauth_round_trip() {
+        let auth = AuthInfo::OAuth(OAuth {
+            refresh: "r-token".into(),
+            access: "a-token".into(),
+            expires: 1_700_000_000_000.0,
+            account_id: Some("acct_1".into()),
+            enterprise_url: None,
+        });
+        let v = serde_json::to_value(&auth).unwrap();
+        assert_eq!(v["type"], "oauth");
+        assert_eq!(v["accountId"], "acct_1");
+        assert!(v.get("enterpriseUrl").is_none());
+        let back: AuthInfo = serde_json::from_value(v).unwrap();
+        assert_eq!(auth, back);
+    }
+
+    #[test]
+    fn auth_info_wellknown_round_trip() {
+        let raw = json!({ "type": "wellknown", "key": "CORP_TOKEN", "token": "t-123" });
+        let auth: AuthInfo = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(
+            auth,
+            AuthInfo::WellKnown(WellKnownAuth { key: "CORP_TOKEN".into(), token: "t-123".into() })
+        );
+        assert_eq!(serde_json::to_value(&auth).unwrap(), raw);
+    }
+}
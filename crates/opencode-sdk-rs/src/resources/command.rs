@@ -0,0 +1,115 @@
+//! Command resource types and methods mirroring the JS SDK's
+//! `resources/command.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Where a slash command was defined.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandSource {
+    /// A command from the configuration or a command file.
+    Command,
+    /// A prompt exposed by an MCP server.
+    Mcp,
+    /// A skill exposed as a command.
+    Skill,
+}
+
+/// A slash command that can be run in a session.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommandInfo {
+    /// Command name, without the leading `/`.
+    pub name: String,
+    /// Human-readable description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Agent the command runs with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Model the command runs with, as `provider/model`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Where the command was defined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<CommandSource>,
+    /// Prompt template, with `$ARGUMENTS` / `$1`… placeholders.
+    pub template: String,
+    /// Whether the command runs as a subtask.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtask: Option<bool>,
+    /// Argument placeholders found in the template.
+    pub hints: Vec<String>,
+}
+
+/// Response type for [`CommandResource::list`].
+pub type CommandListResponse = Vec<CommandInfo>;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/command` endpoint.
+pub struct CommandResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> CommandResource<'a> {
+    /// Create a new `CommandResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List all available slash commands (`GET /command`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<CommandListResponse, OpencodeError> {
+        self.client.get("/command", options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn command_info_round_trip() {
+        let raw = json!({
+            "name": "review",
+            "description": "Review the current diff",
+            "agent": "plan",
+            "model": "anthropic/claude-sonnet-4",
+            "source": "command",
+            "template": "Review the changes in $1",
+            "subtask": true,
+            "hints": ["$1"]
+        });
+        let command: CommandInfo = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(command.source, Some(CommandSource::Command));
+        assert_eq!(command.subtask, Some(true));
+        assert_eq!(serde_json::to_value(&command).unwrap(), raw);
+    }
+
+    #[test]
+    fn command_info_minimal() {
+        let raw = r#"{"name":"init","template":"Create AGENTS.md","hints":[]}"#;
+        let command: CommandInfo = serde_json::from_str(raw).unwrap();
+        assert!(command.source.is_none());
+        assert!(command.hints.is_empty());
+    }
+}
@@ -461,6 +461,144 @@ pub struct Config {
     pub username: Option<String>,
 }
 
+// ---------------------------------------------------------------------------
+// Config patch
+// ---------------------------------------------------------------------------
+
+/// Partial configuration sent to [`ConfigResource::update`].
+///
+/// Serialises exactly like [`Config`] with only the fields that were set;
+/// the server merges it into the current configuration.  Map-valued setters
+/// such as [`mcp`](Self::mcp) add or replace a single entry, leaving the
+/// other entries on the server untouched.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(transparent)]
+pub struct ConfigPatch {
+    config: Config,
+}
+
+impl ConfigPatch {
+    /// Create an empty patch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the default model (`provider/model`).
+    #[must_use]
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.config.model = Some(model.into());
+        self
+    }
+
+    /// Set the small model used for titles and summaries (`provider/model`).
+    #[must_use]
+    pub fn small_model(mut self, model: impl Into<String>) -> Self {
+        self.config.small_model = Some(model.into());
+        self
+    }
+
+    /// Set the UI theme.
+    #[must_use]
+    pub fn theme(mut self, theme: impl Into<String>) -> Self {
+        self.config.theme = Some(theme.into());
+        self
+    }
+
+    /// Set the display username.
+    #[must_use]
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.config.username = Some(username.into());
+        self
+    }
+
+    /// Set the session sharing mode.
+    #[must_use]
+    pub const fn share(mut self, share: ShareMode) -> Self {
+        self.config.share = Some(share);
+        self
+    }
+
+    /// Replace the custom instruction files.
+    #[must_use]
+    pub fn instructions<I, S>(mut self, instructions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.instructions = Some(instructions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Replace the list of disabled providers.
+    #[must_use]
+    pub fn disabled_providers<I, S>(mut self, providers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.disabled_providers = Some(providers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the keybinding configuration.
+    #[must_use]
+    pub fn keybinds(mut self, keybinds: KeybindsConfig) -> Self {
+        self.config.keybinds = Some(keybinds);
+        self
+    }
+
+    /// Set the experimental feature flags.
+    #[must_use]
+    pub fn experimental(mut self, experimental: Experimental) -> Self {
+        self.config.experimental = Some(experimental);
+        self
+    }
+
+    /// Add or replace the settings of one agent.
+    #[must_use]
+    pub fn agent(mut self, name: impl Into<String>, agent: AgentConfig) -> Self {
+        self.config.agent.get_or_insert_with(HashMap::new).insert(name.into(), agent);
+        self
+    }
+
+    /// Add or replace one MCP server.
+    #[must_use]
+    pub fn mcp(mut self, name: impl Into<String>, mcp: McpConfig) -> Self {
+        self.config.mcp.get_or_insert_with(HashMap::new).insert(name.into(), mcp);
+        self
+    }
+
+    /// Add or replace the settings of one mode.
+    #[must_use]
+    pub fn mode(mut self, name: impl Into<String>, mode: ModeConfig) -> Self {
+        self.config.mode.get_or_insert_with(HashMap::new).insert(name.into(), mode);
+        self
+    }
+
+    /// Add or replace one provider.
+    #[must_use]
+    pub fn provider(mut self, name: impl Into<String>, provider: ProviderConfig) -> Self {
+        self.config.provider.get_or_insert_with(HashMap::new).insert(name.into(), provider);
+        self
+    }
+
+    /// The configuration fields set so far.
+    pub const fn as_config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Whether no field has been set.
+    pub fn is_empty(&self) -> bool {
+        self.config == Config::default()
+    }
+}
+
+impl From<Config> for ConfigPatch {
+    fn from(config: Config) -> Self {
+        Self { config }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Resource
 // ---------------------------------------------------------------------------
@@ -481,6 +619,16 @@ impl<'a> ConfigResource<'a> {
     pub async fn get(&self, options: Option<&RequestOptions>) -> Result<Config, OpencodeError> {
         self.client.get("/config", options).await
     }
+
+    /// Merge a partial configuration into the running server's config and
+    /// return the result (`PATCH /config`).
+    pub async fn update(
+        &self,
+        patch: &ConfigPatch,
+        options: Option<&RequestOptions>,
+    ) -> Result<Config, OpencodeError> {
+        self.client.patch("/config", Some(patch), options).await
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -493,6 +641,48 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn config_patch_serializes_only_set_fields() {
+        assert!(ConfigPatch::new().is_empty());
+        assert_eq!(serde_json::to_value(ConfigPatch::new()).unwrap(), json!({}));
+
+        let patch = ConfigPatch::new()
+            .model("anthropic/claude-sonnet-4")
+            .share(ShareMode::Disabled)
+            .mcp(
+                "docs",
+                McpConfig::Remote(McpRemoteConfig {
+                    url: "https://mcp.example.com".into(),
+                    enabled: Some(true),
+                    headers: None,
+                }),
+            )
+            .mode("plan", ModeConfig { temperature: Some(0.2), ..ModeConfig::default() });
+        assert!(!patch.is_empty());
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            json!({
+                "model": "anthropic/claude-sonnet-4",
+                "share": "disabled",
+                "mcp": {
+                    "docs": { "type": "remote", "url": "https://mcp.example.com", "enabled": true }
+                },
+                "mode": { "plan": { "temperature": 0.2 } }
+            })
+        );
+    }
+
+    #[test]
+    fn config_patch_map_setters_accumulate() {
+        let patch = ConfigPatch::new()
+            .instructions(["AGENTS.md"])
+            .mode("build", ModeConfig::default())
+            .mode("plan", ModeConfig::default());
+        let config = patch.as_config();
+        assert_eq!(config.mode.as_ref().map(HashMap::len), Some(2));
+        assert_eq!(config.instructions.as_deref(), Some(&["AGENTS.md".to_owned()][..]));
+    }
+
     #[test]
     fn mode_config_round_trip() {
         let mc = ModeConfig {
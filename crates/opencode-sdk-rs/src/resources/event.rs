@@ -7,10 +7,13 @@
 use serde::{Deserialize, Serialize};
 
 use super::{
-    session::{FileDiff, Message, Part, Session},
+    permission::PermissionRequest,
+    project::Project,
+    question::QuestionRequest,
+    session::{FileDiff, Message, Part, Session, SessionStatus},
     shared::SessionError,
 };
-use crate::client::Opencode;
+use crate::client::{Opencode, RequestOptions};
 
 // ---------------------------------------------------------------------------
 // EventListResponse — internally-tagged discriminated union
@@ -18,7 +21,9 @@ use crate::client::Opencode;
 
 /// A single event from the `/event` SSE stream.
 ///
-/// Internally tagged on `"type"` to match the JS SDK representation.
+/// Internally tagged on `"type"` to match the JS SDK representation.  Events
+/// that cannot be decoded into a typed variant become
+/// [`EventListResponse::Unknown`] instead of failing.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum EventListResponse {
@@ -42,7 +47,7 @@ pub enum EventListResponse {
     #[serde(rename = "project.updated")]
     ProjectUpdated {
         /// Payload.
-        properties: ProjectUpdatedProps,
+        properties: Project,
     },
 
     // ----- server -----
@@ -138,8 +143,8 @@ pub enum EventListResponse {
     /// A permission was asked.
     #[serde(rename = "permission.asked")]
     PermissionAsked {
-        /// Payload (complex `PermissionRequest` type, represented as JSON value).
-        properties: serde_json::Value,
+        /// Payload.
+        properties: PermissionRequest,
     },
 
     /// A permission was replied to.
@@ -210,8 +215,8 @@ pub enum EventListResponse {
     /// A question was asked.
     #[serde(rename = "question.asked")]
     QuestionAsked {
-        /// Payload (complex `QuestionRequest` type, represented as JSON value).
-        properties: serde_json::Value,
+        /// Payload.
+        properties: QuestionRequest,
     },
 
     /// A question was replied to.
@@ -339,6 +344,53 @@ pub enum EventListResponse {
         /// Payload.
         properties: WorktreeFailedProps,
     },
+
+    // ----- forward compatibility -----
+    /// An event this SDK version cannot decode, kept verbatim.
+    ///
+    /// Produced for event types added in newer server versions, and for
+    /// known types whose payload no longer matches the typed schema, so a
+    /// server upgrade never ends an event loop.
+    #[serde(untagged)]
+    Unknown {
+        /// The event's `type` tag.
+        #[serde(rename = "type")]
+        event_type: String,
+        /// The raw event payload.
+        #[serde(default)]
+        properties: serde_json::Value,
+    },
+}
+
+impl EventListResponse {
+    /// The session this event concerns, or `None` for events that are not
+    /// tied to a session (installation, project, server, PTY, …).
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::MessageUpdated { properties } => Some(properties.info.session_id()),
+            Self::MessagePartUpdated { properties } => properties.part.session_id(),
+            Self::SessionCreated { properties } => Some(&properties.info.id),
+            Self::SessionUpdated { properties } => Some(&properties.info.id),
+            Self::SessionDeleted { properties } => Some(&properties.info.id),
+            Self::SessionError { properties } => properties.session_id.as_deref(),
+            Self::PermissionAsked { properties } => Some(&properties.session_id),
+            Self::QuestionAsked { properties } => Some(&properties.session_id),
+            Self::MessageRemoved { properties } => Some(&properties.session_id),
+            Self::MessagePartDelta { properties } => Some(&properties.session_id),
+            Self::MessagePartRemoved { properties } => Some(&properties.session_id),
+            Self::PermissionReplied { properties } => Some(&properties.session_id),
+            Self::SessionStatus { properties } => Some(&properties.session_id),
+            Self::SessionIdle { properties } => Some(&properties.session_id),
+            Self::SessionDiff { properties } => Some(&properties.session_id),
+            Self::SessionCompacted { properties } => Some(&properties.session_id),
+            Self::QuestionReplied { properties } => Some(&properties.session_id),
+            Self::QuestionRejected { properties } => Some(&properties.session_id),
+            Self::TodoUpdated { properties } => Some(&properties.session_id),
+            Self::TuiSessionSelect { properties } => Some(&properties.session_id),
+            Self::CommandExecuted { properties } => Some(&properties.session_id),
+            _ => None,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -363,16 +415,6 @@ pub struct InstallationUpdateAvailableProps {
     pub version: String,
 }
 
-/// Properties for [`EventListResponse::ProjectUpdated`].
-///
-/// The `Project` type is complex; represented as a JSON value.
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct ProjectUpdatedProps {
-    /// The updated project (complex type, serialised as `serde_json::Value`).
-    pub properties: serde_json::Value,
-}
-
 /// Properties for [`EventListResponse::ServerInstanceDisposed`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ServerInstanceDisposedProps {
@@ -496,14 +538,13 @@ pub struct SessionDeletedProps {
 }
 
 /// Properties for [`EventListResponse::SessionStatus`].
-#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SessionStatusProps {
     /// Session ID.
     #[serde(rename = "sessionID")]
     pub session_id: String,
-    /// Session status (complex tagged union, represented as `serde_json::Value`).
-    pub status: serde_json::Value,
+    /// New session status.
+    pub status: SessionStatus,
 }
 
 /// Properties for [`EventListResponse::SessionIdle`].
@@ -811,8 +852,28 @@ impl<'a> EventResource<'a> {
     /// each event's `data` field is a JSON-encoded [`EventListResponse`].
     pub async fn list(
         &self,
+        options: Option<&RequestOptions>,
     ) -> Result<crate::streaming::SseStream<EventListResponse>, crate::error::OpencodeError> {
-        self.client.get_stream("/event").await
+        self.client.get_stream("/event", options).await
+    }
+
+    /// List events as an SSE stream that reconnects when the connection
+    /// drops, e.g. across server restarts.
+    ///
+    /// Each reconnection is reported as [`SseItem::Reconnected`]; events
+    /// emitted while disconnected are not replayed, so refresh any derived
+    /// state (sessions, statuses, …) when it arrives.
+    ///
+    /// [`SseItem::Reconnected`]: crate::streaming::SseItem::Reconnected
+    pub async fn list_reconnecting(
+        &self,
+        reconnect: crate::streaming::ReconnectOptions,
+        options: Option<&RequestOptions>,
+    ) -> Result<
+        crate::streaming::ReconnectingSseStream<EventListResponse>,
+        crate::error::OpencodeError,
+    > {
+        self.client.get_stream_reconnecting("/event", reconnect, options).await
     }
 }
 
@@ -951,14 +1012,21 @@ mod tests {
             "properties": {
                 "id": "perm_001",
                 "sessionID": "sess_001",
-                "title": "Run bash command"
+                "permission": "bash",
+                "patterns": ["ls -la"],
+                "metadata": { "command": "ls -la" },
+                "always": ["ls *"],
+                "tool": { "messageID": "msg_001", "callID": "call_001" }
             }
         }"#;
         let event: EventListResponse = serde_json::from_str(raw).unwrap();
         match &event {
             EventListResponse::PermissionAsked { properties } => {
-                assert_eq!(properties["id"], "perm_001");
-                assert_eq!(properties["sessionID"], "sess_001");
+                assert_eq!(properties.id, "perm_001");
+                assert_eq!(properties.session_id, "sess_001");
+                assert_eq!(properties.permission, "bash");
+                assert_eq!(properties.always, vec!["ls *".to_owned()]);
+                assert_eq!(properties.tool.as_ref().map(|t| t.call_id.as_str()), Some("call_001"));
             }
             other => panic!("expected PermissionAsked, got {other:?}"),
         }
@@ -989,6 +1057,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn permission_replied_round_trip() {
+        for reply in [PermissionReply::Once, PermissionReply::Always, PermissionReply::Reject] {
+            let event = EventListResponse::PermissionReplied {
+                properties: PermissionRepliedProps {
+                    session_id: "sess_001".into(),
+                    request_id: "per_001".into(),
+                    reply,
+                },
+            };
+            let json_str = serde_json::to_string(&event).unwrap();
+            assert!(json_str.contains(r#""type":"permission.replied"#));
+            assert!(json_str.contains(r#""requestID":"per_001"#));
+            let back: EventListResponse = serde_json::from_str(&json_str).unwrap();
+            assert_eq!(event, back);
+        }
+    }
+
     // -- Missing event variant round-trips --
 
     #[test]
@@ -1280,6 +1366,37 @@ mod tests {
         assert_eq!(event, back);
     }
 
+    #[test]
+    fn question_asked_round_trip() {
+        let raw = r#"{
+            "type": "question.asked",
+            "properties": {
+                "id": "que_001",
+                "sessionID": "sess_001",
+                "questions": [{
+                    "question": "Run the migrations now?",
+                    "header": "Migrations",
+                    "options": [
+                        { "label": "Yes", "description": "Run them" },
+                        { "label": "No", "description": "Skip for now" }
+                    ],
+                    "multiple": false
+                }]
+            }
+        }"#;
+        let event: EventListResponse = serde_json::from_str(raw).unwrap();
+        match &event {
+            EventListResponse::QuestionAsked { properties } => {
+                assert_eq!(properties.id, "que_001");
+                assert_eq!(properties.questions[0].options.len(), 2);
+            }
+            other => panic!("expected QuestionAsked, got {other:?}"),
+        }
+        let json_str = serde_json::to_string(&event).unwrap();
+        let back: EventListResponse = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(event, back);
+    }
+
     #[test]
     fn question_replied_round_trip() {
         let event = EventListResponse::QuestionReplied {
@@ -1374,13 +1491,19 @@ mod tests {
         let raw = r#"{
             "type": "project.updated",
             "properties": {
-                "properties": { "name": "my-project", "path": "/tmp/proj" }
+                "id": "prj_001",
+                "worktree": "/tmp/proj",
+                "vcs": "git",
+                "name": "my-project",
+                "time": { "created": 1700000000000, "updated": 1700000100000 },
+                "sandboxes": []
             }
         }"#;
         let event: EventListResponse = serde_json::from_str(raw).unwrap();
         match &event {
             EventListResponse::ProjectUpdated { properties } => {
-                assert_eq!(properties.properties["name"], "my-project");
+                assert_eq!(properties.name.as_deref(), Some("my-project"));
+                assert_eq!(properties.worktree, "/tmp/proj");
             }
             other => panic!("expected ProjectUpdated, got {other:?}"),
         }
@@ -1392,14 +1515,79 @@ mod tests {
             "type": "session.status",
             "properties": {
                 "sessionID": "sess_001",
-                "status": { "type": "running", "tool": "bash" }
+                "status": { "type": "retry", "attempt": 1, "message": "overloaded", "next": 5000 }
             }
         }"#;
         let event: EventListResponse = serde_json::from_str(raw).unwrap();
         match &event {
             EventListResponse::SessionStatus { properties } => {
                 assert_eq!(properties.session_id, "sess_001");
-                assert_eq!(properties.status["type"], "running");
+                assert!(
+                    matches!(properties.status, SessionStatus::Retry { attempt, .. } if attempt == 1.0)
+                );
+            }
+            other => panic!("expected SessionStatus, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_event_type_preserves_payload() {
+        let raw = serde_json::json!({
+            "type": "session.archived",
+            "properties": { "sessionID": "sess_001", "reason": "inactive" }
+        });
+        let event: EventListResponse = serde_json::from_value(raw.clone()).unwrap();
+        match &event {
+            EventListResponse::Unknown { event_type, properties } => {
+                assert_eq!(event_type, "session.archived");
+                assert_eq!(properties["reason"], "inactive");
+            }
+            other => panic!("expected Unknown, got {other:?}"),
+        }
+        assert_eq!(serde_json::to_value(&event).unwrap(), raw);
+    }
+
+    #[test]
+    fn unknown_event_without_properties() {
+        let event: EventListResponse =
+            serde_json::from_str(r#"{"type":"server.heartbeat"}"#).unwrap();
+        assert_eq!(
+            event,
+            EventListResponse::Unknown {
+                event_type: "server.heartbeat".into(),
+                properties: serde_json::Value::Null,
+            }
+        );
+    }
+
+    #[test]
+    fn known_event_with_incompatible_payload_is_unknown() {
+        let raw = r#"{"type":"vcs.branch.updated","properties":{"branch":42}}"#;
+        let event: EventListResponse = serde_json::from_str(raw).unwrap();
+        assert!(matches!(
+            event,
+            EventListResponse::Unknown { ref event_type, .. } if event_type == "vcs.branch.updated"
+        ));
+    }
+
+    #[test]
+    fn event_without_type_is_an_error() {
+        assert!(serde_json::from_str::<EventListResponse>(r#"{"properties":{}}"#).is_err());
+    }
+
+    #[test]
+    fn deserialize_session_status_unknown_type() {
+        let raw = r#"{
+            "type": "session.status",
+            "properties": {
+                "sessionID": "sess_001",
+                "status": { "type": "running", "tool": "bash" }
+            }
+        }"#;
+        let event: EventListResponse = serde_json::from_str(raw).unwrap();
+        match &event {
+            EventListResponse::SessionStatus { properties } => {
+                assert_eq!(properties.status, SessionStatus::Unknown);
             }
             other => panic!("expected SessionStatus, got {other:?}"),
         }
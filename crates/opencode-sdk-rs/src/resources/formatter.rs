@@ -0,0 +1,72 @@
+//! Formatter resource types and methods mirroring the JS SDK's
+//! `resources/formatter.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Status of a single code formatter.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FormatterStatus {
+    /// Formatter name, e.g. `prettier`.
+    pub name: String,
+    /// File extensions the formatter handles, e.g. `.ts`.
+    pub extensions: Vec<String>,
+    /// Whether the formatter is available and enabled.
+    pub enabled: bool,
+}
+
+/// Response type for [`FormatterResource::status`].
+pub type FormatterStatusResponse = Vec<FormatterStatus>;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/formatter` endpoint.
+pub struct FormatterResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> FormatterResource<'a> {
+    /// Create a new `FormatterResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Get the status of all formatters (`GET /formatter`).
+    pub async fn status(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<FormatterStatusResponse, OpencodeError> {
+        self.client.get("/formatter", options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatter_status_round_trip() {
+        let status = FormatterStatus {
+            name: "rustfmt".into(),
+            extensions: vec![".rs".into()],
+            enabled: true,
+        };
+        let json_str = serde_json::to_string(&status).unwrap();
+        let back: FormatterStatus = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(status, back);
+    }
+}
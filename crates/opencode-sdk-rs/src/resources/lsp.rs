@@ -0,0 +1,183 @@
+//! LSP resource types and methods mirroring the JS SDK's `resources/lsp.ts`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::find::Range;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Connection state of a language server.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LspServerStatus {
+    /// The server is running and connected.
+    Connected,
+    /// The server failed to start or crashed.
+    Error,
+    /// Any unknown status returned by newer server versions.
+    #[serde(other)]
+    Unknown,
+}
+
+/// Status of a single language server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LspStatus {
+    /// Server identifier, as in
+    /// [`LspClientDiagnosticsProps::server_id`](super::event::LspClientDiagnosticsProps::server_id).
+    pub id: String,
+    /// Human-readable server name.
+    pub name: String,
+    /// Workspace root the server was started in.
+    pub root: String,
+    /// Connection state.
+    pub status: LspServerStatus,
+}
+
+/// Response type for [`LspResource::status`].
+pub type LspStatusResponse = Vec<LspStatus>;
+
+/// Severity of an [`LspDiagnostic`], encoded as in the LSP specification.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(from = "u8", into = "u8")]
+pub enum LspDiagnosticSeverity {
+    /// `1` — an error.
+    Error,
+    /// `2` — a warning.
+    Warning,
+    /// `3` — an informational message.
+    Information,
+    /// `4` — a hint.
+    Hint,
+    /// Any other value, kept as sent by newer servers.
+    Unknown(u8),
+}
+
+impl From<u8> for LspDiagnosticSeverity {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Error,
+            2 => Self::Warning,
+            3 => Self::Information,
+            4 => Self::Hint,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+impl From<LspDiagnosticSeverity> for u8 {
+    fn from(value: LspDiagnosticSeverity) -> Self {
+        match value {
+            LspDiagnosticSeverity::Error => 1,
+            LspDiagnosticSeverity::Warning => 2,
+            LspDiagnosticSeverity::Information => 3,
+            LspDiagnosticSeverity::Hint => 4,
+            LspDiagnosticSeverity::Unknown(value) => value,
+        }
+    }
+}
+
+/// A diagnostic reported by a language server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LspDiagnostic {
+    /// Range the diagnostic applies to.
+    pub range: Range,
+    /// Severity, if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<LspDiagnosticSeverity>,
+    /// Diagnostic code (a number or a string).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<serde_json::Value>,
+    /// Tool that produced the diagnostic, e.g. `typescript`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Diagnostic message.
+    pub message: String,
+}
+
+/// Diagnostics keyed by absolute file path.
+///
+/// The server attaches this map to edit and write tool results; see
+/// [`ToolStateCompleted::diagnostics`](super::session::ToolStateCompleted::diagnostics).
+pub type LspDiagnostics = HashMap<String, Vec<LspDiagnostic>>;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/lsp` endpoint.
+pub struct LspResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> LspResource<'a> {
+    /// Create a new `LspResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Get the status of all language servers (`GET /lsp`).
+    pub async fn status(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<LspStatusResponse, OpencodeError> {
+        self.client.get("/lsp", options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn lsp_status_deserialize() {
+        let raw = json!([
+            { "id": "typescript", "name": "TypeScript", "root": "/repo", "status": "connected" },
+            { "id": "rust", "name": "rust-analyzer", "root": "/repo", "status": "error" },
+            { "id": "go", "name": "gopls", "root": "/repo", "status": "starting" }
+        ]);
+        let status: LspStatusResponse = serde_json::from_value(raw).unwrap();
+        assert_eq!(status[0].status, LspServerStatus::Connected);
+        assert_eq!(status[1].status, LspServerStatus::Error);
+        assert_eq!(status[2].status, LspServerStatus::Unknown);
+    }
+
+    #[test]
+    fn lsp_diagnostic_round_trip() {
+        let raw = json!({
+            "range": {
+                "start": { "line": 3, "character": 4 },
+                "end": { "line": 3, "character": 9 }
+            },
+            "severity": 1,
+            "code": 2304,
+            "source": "typescript",
+            "message": "Cannot find name 'foo'."
+        });
+        let diagnostic: LspDiagnostic = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(diagnostic.severity, Some(LspDiagnosticSeverity::Error));
+        assert_eq!(diagnostic.range.start.line, 3);
+        assert_eq!(serde_json::to_value(&diagnostic).unwrap(), raw);
+    }
+
+    #[test]
+    fn lsp_diagnostic_severity_keeps_unknown() {
+        let severity: LspDiagnosticSeverity = serde_json::from_value(json!(5)).unwrap();
+        assert_eq!(severity, LspDiagnosticSeverity::Unknown(5));
+        assert_eq!(serde_json::to_value(severity).unwrap(), json!(5));
+        assert!(LspDiagnosticSeverity::Error < LspDiagnosticSeverity::Hint);
+    }
+}
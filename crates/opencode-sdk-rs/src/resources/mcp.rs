@@ -0,0 +1,257 @@
+//! MCP resource types and methods mirroring the JS SDK's `resources/mcp.ts`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::McpConfig;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Connection status of a single MCP server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "status")]
+pub enum McpStatus {
+    /// The server is connected and its tools are available.
+    #[serde(rename = "connected")]
+    Connected,
+    /// The server is disabled in the configuration.
+    #[serde(rename = "disabled")]
+    Disabled,
+    /// The server failed to start or connect.
+    #[serde(rename = "failed")]
+    Failed {
+        /// Failure description.
+        error: String,
+    },
+    /// The server requires OAuth authentication.
+    #[serde(rename = "needs_auth")]
+    NeedsAuth,
+    /// The server requires OAuth dynamic client registration.
+    #[serde(rename = "needs_client_registration")]
+    NeedsClientRegistration {
+        /// Registration failure description.
+        error: String,
+    },
+}
+
+/// Response type for [`McpResource::status`] and [`McpResource::add`]: status
+/// keyed by server name.
+pub type McpStatusResponse = HashMap<String, McpStatus>;
+
+/// Response type for [`McpResource::auth_start`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct McpAuthStartResponse {
+    /// URL to open in a browser to authorize the server.
+    #[serde(rename = "authorizationUrl")]
+    pub authorization_url: String,
+}
+
+/// Response type for [`McpResource::auth_remove`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct McpAuthRemoveResponse {
+    /// Always `true` on success.
+    pub success: bool,
+}
+
+/// Response type for [`McpResource::connect`].
+pub type McpConnectResponse = bool;
+
+/// Response type for [`McpResource::disconnect`].
+pub type McpDisconnectResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`McpResource::add`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct McpAddParams {
+    /// Name to register the server under.
+    pub name: String,
+    /// Server configuration.
+    pub config: McpConfig,
+}
+
+/// Parameters for [`McpResource::auth_callback`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct McpAuthCallbackParams {
+    /// Authorization code from the OAuth callback.
+    pub code: String,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/mcp` endpoints.
+pub struct McpResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> McpResource<'a> {
+    /// Create a new `McpResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Get the status of all MCP servers (`GET /mcp`).
+    pub async fn status(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpStatusResponse, OpencodeError> {
+        self.client.get("/mcp", options).await
+    }
+
+    /// Register a new MCP server at runtime (`POST /mcp`).
+    pub async fn add(
+        &self,
+        params: &McpAddParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpStatusResponse, OpencodeError> {
+        self.client.post("/mcp", Some(params), options).await
+    }
+
+    /// Connect an MCP server (`POST /mcp/{name}/connect`).
+    pub async fn connect(
+        &self,
+        name: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpConnectResponse, OpencodeError> {
+        self.client.post::<bool, ()>(&encode_path!("/mcp/{}/connect", name), None, options).await
+    }
+
+    /// Disconnect an MCP server (`POST /mcp/{name}/disconnect`).
+    pub async fn disconnect(
+        &self,
+        name: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpDisconnectResponse, OpencodeError> {
+        self.client.post::<bool, ()>(&encode_path!("/mcp/{}/disconnect", name), None, options).await
+    }
+
+    /// Start the OAuth flow for an MCP server (`POST /mcp/{name}/auth`).
+    ///
+    /// Open the returned URL in a browser, then finish with
+    /// [`auth_callback`](Self::auth_callback).
+    pub async fn auth_start(
+        &self,
+        name: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpAuthStartResponse, OpencodeError> {
+        self.client
+            .post::<McpAuthStartResponse, ()>(&encode_path!("/mcp/{}/auth", name), None, options)
+            .await
+    }
+
+    /// Complete the OAuth flow with an authorization code
+    /// (`POST /mcp/{name}/auth/callback`).
+    pub async fn auth_callback(
+        &self,
+        name: &str,
+        params: &McpAuthCallbackParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpStatus, OpencodeError> {
+        self.client.post(&encode_path!("/mcp/{}/auth/callback", name), Some(params), options).await
+    }
+
+    /// Start the OAuth flow and wait for the callback on the server side
+    /// (`POST /mcp/{name}/auth/authenticate`).
+    ///
+    /// The server opens the browser itself, so this only works when it runs
+    /// on the user's machine.
+    pub async fn authenticate(
+        &self,
+        name: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpStatus, OpencodeError> {
+        self.client
+            .post::<McpStatus, ()>(&encode_path!("/mcp/{}/auth/authenticate", name), None, options)
+            .await
+    }
+
+    /// Remove stored OAuth credentials for an MCP server
+    /// (`DELETE /mcp/{name}/auth`).
+    pub async fn auth_remove(
+        &self,
+        name: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<McpAuthRemoveResponse, OpencodeError> {
+        self.client
+            .delete::<McpAuthRemoveResponse, ()>(&encode_path!("/mcp/{}/auth", name), None, options)
+            .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::resources::config::McpRemoteConfig;
+
+    #[test]
+    fn mcp_status_variants() {
+        let raw = json!({
+            "github": { "status": "connected" },
+            "jira": { "status": "needs_auth" },
+            "local": { "status": "failed", "error": "spawn ENOENT" },
+            "off": { "status": "disabled" },
+            "sso": { "status": "needs_client_registration", "error": "no registration endpoint" }
+        });
+        let status: McpStatusResponse = serde_json::from_value(raw).unwrap();
+        assert_eq!(status["github"], McpStatus::Connected);
+        assert_eq!(status["jira"], McpStatus::NeedsAuth);
+        assert_eq!(status["local"], McpStatus::Failed { error: "spawn ENOENT".into() });
+        assert_eq!(status["off"], McpStatus::Disabled);
+        assert!(matches!(status["sso"], McpStatus::NeedsClientRegistration { .. }));
+    }
+
+    #[test]
+    fn mcp_status_round_trip() {
+        let status = McpStatus::Failed { error: "timeout".into() };
+        let v = serde_json::to_value(&status).unwrap();
+        assert_eq!(v, json!({ "status": "failed", "error": "timeout" }));
+        let back: McpStatus = serde_json::from_value(v).unwrap();
+        assert_eq!(status, back);
+    }
+
+    #[test]
+    fn mcp_add_params_serialize() {
+        let params = McpAddParams {
+            name: "docs".into(),
+            config: McpConfig::Remote(McpRemoteConfig {
+                url: "https://mcp.example.com".into(),
+                enabled: None,
+                headers: None,
+            }),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            v,
+            json!({
+                "name": "docs",
+                "config": { "type": "remote", "url": "https://mcp.example.com" }
+            })
+        );
+    }
+
+    #[test]
+    fn mcp_auth_start_response_deserialize() {
+        let resp: McpAuthStartResponse =
+            serde_json::from_str(r#"{"authorizationUrl":"https://auth.example.com/authorize"}"#)
+                .unwrap();
+        assert_eq!(resp.authorization_url, "https://auth.example.com/authorize");
+    }
+}
@@ -1,26 +1,54 @@
 //! API resource modules, one per endpoint group.
 
+pub mod agent;
 pub mod app;
+pub mod auth;
+pub mod command;
 pub mod config;
 pub mod event;
 pub mod file;
 pub mod find;
+pub mod formatter;
+pub mod lsp;
+pub mod mcp;
+pub mod permission;
+pub mod project;
+pub mod provider;
+pub mod pty;
+pub mod question;
 pub mod session;
 pub mod shared;
+pub mod skill;
 pub mod tui;
+pub mod vcs;
+pub mod worktree;
 
 // Re-export all types for convenience
+pub use agent::*;
 pub use app::*;
+pub use auth::*;
+pub use command::*;
 // Re-export config types explicitly to avoid ambiguity with `ModelCost` / `ModelLimit`
 // which are already re-exported from `app`.
 pub use config::{
-    Agent, AgentConfig, Config, ConfigResource, Experimental, Hook, HookCommand, KeybindsConfig,
-    Layout, McpConfig, McpLocalConfig, McpRemoteConfig, ModeConfig, ModeMap, ProviderConfig,
-    ProviderModelConfig, ProviderOptions, ShareMode,
+    Agent, AgentConfig, Config, ConfigPatch, ConfigResource, Experimental, Hook, HookCommand,
+    KeybindsConfig, Layout, McpConfig, McpLocalConfig, McpRemoteConfig, ModeConfig, ModeMap,
+    ProviderConfig, ProviderModelConfig, ProviderOptions, ShareMode,
 };
 pub use event::*;
 pub use file::*;
 pub use find::*;
+pub use formatter::*;
+pub use lsp::*;
+pub use mcp::*;
+pub use permission::*;
+pub use project::*;
+pub use provider::*;
+pub use pty::*;
+pub use question::*;
 pub use session::*;
 pub use shared::*;
+pub use skill::*;
 pub use tui::*;
+pub use vcs::*;
+pub use worktree::*;
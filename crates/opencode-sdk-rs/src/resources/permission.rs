@@ -0,0 +1,170 @@
+//! Permission resource types and methods mirroring the JS SDK's `resources/permission.ts`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::event::PermissionReply;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The tool call that triggered a [`PermissionRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PermissionRequestTool {
+    /// The message containing the tool call.
+    #[serde(rename = "messageID")]
+    pub message_id: String,
+    /// The tool call identifier.
+    #[serde(rename = "callID")]
+    pub call_id: String,
+}
+
+/// A pending permission request raised by the assistant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PermissionRequest {
+    /// Unique request identifier (`per…`).
+    pub id: String,
+    /// The session that raised the request.
+    #[serde(rename = "sessionID")]
+    pub session_id: String,
+    /// The permission being requested (e.g. `"bash"`, `"edit"`).
+    pub permission: String,
+    /// Patterns the permission would apply to.
+    pub patterns: Vec<String>,
+    /// Arbitrary request metadata.
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Patterns that would be remembered when replying with
+    /// [`PermissionReply::Always`].
+    #[serde(default)]
+    pub always: Vec<String>,
+    /// The tool call that triggered the request, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<PermissionRequestTool>,
+}
+
+/// Response type for [`PermissionResource::list`].
+pub type PermissionListResponse = Vec<PermissionRequest>;
+
+/// Response type for [`PermissionResource::reply`].
+pub type PermissionReplyResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`PermissionResource::reply`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PermissionReplyParams {
+    /// The reply action.
+    pub reply: PermissionReply,
+    /// Optional message passed back to the assistant (e.g. a rejection reason).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/permission` endpoints.
+pub struct PermissionResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> PermissionResource<'a> {
+    /// Create a new `PermissionResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List pending permission requests across all sessions (`GET /permission`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<PermissionListResponse, OpencodeError> {
+        self.client.get("/permission", options).await
+    }
+
+    /// Reply to a permission request (`POST /permission/{requestID}/reply`).
+    pub async fn reply(
+        &self,
+        request_id: &str,
+        params: &PermissionReplyParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<PermissionReplyResponse, OpencodeError> {
+        self.client
+            .post(&encode_path!("/permission/{}/reply", request_id), Some(params), options)
+            .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn permission_request_round_trip() {
+        let req = PermissionRequest {
+            id: "per_001".into(),
+            session_id: "ses_001".into(),
+            permission: "bash".into(),
+            patterns: vec!["git status".into()],
+            metadata: HashMap::from([("command".into(), json!("git status"))]),
+            always: vec!["git *".into()],
+            tool: Some(PermissionRequestTool {
+                message_id: "msg_001".into(),
+                call_id: "call_001".into(),
+            }),
+        };
+        let json_str = serde_json::to_string(&req).unwrap();
+        assert!(json_str.contains(r#""sessionID":"ses_001"#));
+        assert!(json_str.contains(r#""callID":"call_001"#));
+        let back: PermissionRequest = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(req, back);
+    }
+
+    #[test]
+    fn permission_request_without_tool() {
+        let raw = r#"{
+            "id": "per_002",
+            "sessionID": "ses_001",
+            "permission": "edit",
+            "patterns": ["src/**"],
+            "metadata": {},
+            "always": []
+        }"#;
+        let req: PermissionRequest = serde_json::from_str(raw).unwrap();
+        assert_eq!(req.permission, "edit");
+        assert!(req.tool.is_none());
+        let json_str = serde_json::to_string(&req).unwrap();
+        assert!(!json_str.contains("tool"));
+    }
+
+    #[test]
+    fn permission_reply_params_serialize() {
+        let params = PermissionReplyParams { reply: PermissionReply::Once, message: None };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "reply": "once" }));
+
+        let params = PermissionReplyParams {
+            reply: PermissionReply::Reject,
+            message: Some("not in CI".into()),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "reply": "reject", "message": "not in CI" }));
+    }
+}
@@ -0,0 +1,203 @@
+//! Project resource types and methods mirroring the JS SDK's
+//! `resources/project.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Version control system backing a project.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProjectVcs {
+    /// Git repository.
+    #[serde(rename = "git")]
+    Git,
+}
+
+/// Project icon settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectIcon {
+    /// Icon URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// User-provided icon overriding the detected one.
+    #[serde(rename = "override", skip_serializing_if = "Option::is_none")]
+    pub override_: Option<String>,
+    /// Accent colour.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// Project-level commands.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectCommands {
+    /// Startup script run when creating a new worktree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+}
+
+/// Timestamps for a [`Project`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectTime {
+    /// When the project was first opened.
+    pub created: f64,
+    /// When the project was last updated.
+    pub updated: f64,
+    /// When the project was initialised, if it has been.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initialized: Option<f64>,
+}
+
+/// A project (repository) that has been opened with `OpenCode`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Project {
+    /// Project identifier, referenced by
+    /// [`Session::project_id`](super::session::Session::project_id).
+    pub id: String,
+    /// Root directory of the main worktree.
+    pub worktree: String,
+    /// Version control system, if detected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs: Option<ProjectVcs>,
+    /// Display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Icon settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<ProjectIcon>,
+    /// Project commands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<ProjectCommands>,
+    /// Timestamps.
+    pub time: ProjectTime,
+    /// Sandbox worktree directories.
+    #[serde(default)]
+    pub sandboxes: Vec<String>,
+}
+
+/// Response type for [`ProjectResource::list`].
+pub type ProjectListResponse = Vec<Project>;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`ProjectResource::update`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProjectUpdateParams {
+    /// New display name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New icon settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<ProjectIcon>,
+    /// New project commands.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commands: Option<ProjectCommands>,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/project` endpoints.
+pub struct ProjectResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> ProjectResource<'a> {
+    /// Create a new `ProjectResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List projects that have been opened with `OpenCode` (`GET /project`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<ProjectListResponse, OpencodeError> {
+        self.client.get("/project", options).await
+    }
+
+    /// Retrieve the currently active project (`GET /project/current`).
+    pub async fn current(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<Project, OpencodeError> {
+        self.client.get("/project/current", options).await
+    }
+
+    /// Update a project's name, icon or commands (`PATCH /project/{projectID}`).
+    pub async fn update(
+        &self,
+        id: &str,
+        params: &ProjectUpdateParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Project, OpencodeError> {
+        self.client.patch(&encode_path!("/project/{}", id), Some(params), options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn project_full_round_trip() {
+        let raw = json!({
+            "id": "prj_001",
+            "worktree": "/home/me/repo",
+            "vcs": "git",
+            "name": "repo",
+            "icon": { "override": "data:image/png;base64,AAAA", "color": "#ff8800" },
+            "commands": { "start": "bun install" },
+            "time": { "created": 1_700_000_000_000.0, "updated": 1_700_000_100_000.0 },
+            "sandboxes": ["/home/me/.local/share/opencode/worktree/brave-otter"]
+        });
+        let project: Project = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(project.vcs, Some(ProjectVcs::Git));
+        assert_eq!(
+            project.icon.as_ref().and_then(|i| i.override_.as_deref()),
+            Some("data:image/png;base64,AAAA")
+        );
+        assert_eq!(project.sandboxes.len(), 1);
+        assert_eq!(serde_json::to_value(&project).unwrap(), raw);
+    }
+
+    #[test]
+    fn project_minimal() {
+        let raw = r#"{
+            "id": "global",
+            "worktree": "/",
+            "time": { "created": 1, "updated": 2 },
+            "sandboxes": []
+        }"#;
+        let project: Project = serde_json::from_str(raw).unwrap();
+        assert!(project.vcs.is_none());
+        assert!(project.time.initialized.is_none());
+    }
+
+    #[test]
+    fn project_update_params_serialize() {
+        let params = ProjectUpdateParams {
+            name: Some("renamed".into()),
+            commands: Some(ProjectCommands { start: Some("make setup".into()) }),
+            ..ProjectUpdateParams::default()
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "name": "renamed", "commands": { "start": "make setup" } }));
+    }
+}
@@ -0,0 +1,424 @@
+//! Provider resource types and methods mirroring the JS SDK's
+//! `resources/provider.ts`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::app::{ModelLimit, ModelStatus};
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Input or output modality supported by a model.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Modality {
+    /// Plain text.
+    Text,
+    /// Audio.
+    Audio,
+    /// Images.
+    Image,
+    /// Video.
+    Video,
+    /// PDF documents.
+    Pdf,
+}
+
+/// Modalities a [`ProviderModel`] accepts and produces.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderModelModalities {
+    /// Accepted input modalities.
+    pub input: Vec<Modality>,
+    /// Produced output modalities.
+    pub output: Vec<Modality>,
+}
+
+/// How interleaved reasoning is returned by a model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ProviderModelInterleaved {
+    /// Interleaved reasoning is supported (always `true`).
+    Enabled(bool),
+    /// Reasoning is returned in a dedicated response field.
+    Field {
+        /// Field name, e.g. `reasoning_content` or `reasoning_details`.
+        field: String,
+    },
+}
+
+/// Cost tier applied once the context exceeds 200K tokens.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProviderModelCostTier {
+    /// Cost per input token.
+    pub input: f64,
+    /// Cost per output token.
+    pub output: f64,
+    /// Cost per cache-read token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Cost per cache-write token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+/// Per-token cost information for a [`ProviderModel`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProviderModelCost {
+    /// Cost per input token.
+    pub input: f64,
+    /// Cost per output token.
+    pub output: f64,
+    /// Cost per cache-read token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Cost per cache-write token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    /// Pricing for requests over 200K context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_over_200k: Option<ProviderModelCostTier>,
+}
+
+/// Package and endpoint overrides for a single [`ProviderModel`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderModelPackage {
+    /// AI SDK npm package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub npm: Option<String>,
+    /// API base URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api: Option<String>,
+}
+
+/// A model entry from the provider catalogue (models.dev format).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ProviderModel {
+    /// Model identifier.
+    pub id: String,
+    /// Human-readable model name.
+    pub name: String,
+    /// Model family.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+    /// ISO-8601 release date.
+    pub release_date: String,
+    /// Whether the model accepts file attachments.
+    pub attachment: bool,
+    /// Whether the model supports reasoning.
+    pub reasoning: bool,
+    /// Whether the model accepts a temperature setting.
+    pub temperature: bool,
+    /// Whether the model supports tool calls.
+    pub tool_call: bool,
+    /// Interleaved reasoning support.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interleaved: Option<ProviderModelInterleaved>,
+    /// Cost information per token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<ProviderModelCost>,
+    /// Context and output token limits.
+    pub limit: ModelLimit,
+    /// Supported modalities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalities: Option<ProviderModelModalities>,
+    /// Whether the model is experimental.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<bool>,
+    /// Lifecycle status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ModelStatus>,
+    /// Arbitrary provider-specific options.
+    pub options: HashMap<String, serde_json::Value>,
+    /// Custom headers for API requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    /// Package and endpoint overrides.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<ProviderModelPackage>,
+    /// Model variants.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variants: Option<HashMap<String, HashMap<String, serde_json::Value>>>,
+}
+
+/// A provider from the catalogue, whether or not it is connected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderInfo {
+    /// Provider identifier.
+    pub id: String,
+    /// Human-readable provider name.
+    pub name: String,
+    /// Environment variable names used for authentication.
+    pub env: Vec<String>,
+    /// API base URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api: Option<String>,
+    /// AI SDK npm package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub npm: Option<String>,
+    /// Map of model identifiers to their definitions.
+    pub models: HashMap<String, ProviderModel>,
+}
+
+/// Response type for [`ProviderResource::list`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderListResponse {
+    /// Every known provider.
+    pub all: Vec<ProviderInfo>,
+    /// Default model identifier keyed by provider identifier.
+    pub default: HashMap<String, String>,
+    /// Identifiers of providers that have credentials configured.
+    pub connected: Vec<String>,
+}
+
+/// Kind of authentication a provider supports.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProviderAuthMethodType {
+    /// OAuth authorization flow.
+    #[serde(rename = "oauth")]
+    OAuth,
+    /// Plain API key.
+    #[serde(rename = "api")]
+    Api,
+}
+
+/// An authentication method offered by a provider.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderAuthMethod {
+    /// Method kind.
+    #[serde(rename = "type")]
+    pub kind: ProviderAuthMethodType,
+    /// Label to show the user.
+    pub label: String,
+}
+
+/// Response type for [`ProviderResource::auth`]: auth methods keyed by
+/// provider identifier.  A method's position in its list is the index passed
+/// as `method` to the OAuth endpoints.
+pub type ProviderAuthResponse = HashMap<String, Vec<ProviderAuthMethod>>;
+
+/// How an OAuth authorization is completed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProviderAuthAuthorizationMethod {
+    /// The server receives the callback itself; call
+    /// [`ProviderResource::oauth_callback`] without a code.
+    #[serde(rename = "auto")]
+    Auto,
+    /// The user pastes a code that must be passed to
+    /// [`ProviderResource::oauth_callback`].
+    #[serde(rename = "code")]
+    Code,
+}
+
+/// Response type for [`ProviderResource::oauth_authorize`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderAuthAuthorization {
+    /// URL to open in a browser.
+    pub url: String,
+    /// How the authorization is completed.
+    pub method: ProviderAuthAuthorizationMethod,
+    /// Instructions to show the user.
+    pub instructions: String,
+}
+
+/// Response type for [`ProviderResource::oauth_callback`].
+pub type ProviderOauthCallbackResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`ProviderResource::oauth_authorize`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderOauthAuthorizeParams {
+    /// Index of the auth method in the provider's
+    /// [`auth`](ProviderResource::auth) list.
+    pub method: u32,
+}
+
+/// Parameters for [`ProviderResource::oauth_callback`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderOauthCallbackParams {
+    /// Index of the auth method used to authorize.
+    pub method: u32,
+    /// Authorization code, required for
+    /// [`ProviderAuthAuthorizationMethod::Code`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/provider` endpoints.
+pub struct ProviderResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> ProviderResource<'a> {
+    /// Create a new `ProviderResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List all known providers along with defaults and connection state
+    /// (`GET /provider`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<ProviderListResponse, OpencodeError> {
+        self.client.get("/provider", options).await
+    }
+
+    /// List the authentication methods each provider supports
+    /// (`GET /provider/auth`).
+    pub async fn auth(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<ProviderAuthResponse, OpencodeError> {
+        self.client.get("/provider/auth", options).await
+    }
+
+    /// Start an OAuth authorization for a provider
+    /// (`POST /provider/{providerID}/oauth/authorize`).
+    ///
+    /// Open the returned URL in a browser, then finish with
+    /// [`oauth_callback`](Self::oauth_callback).
+    pub async fn oauth_authorize(
+        &self,
+        provider_id: &str,
+        params: &ProviderOauthAuthorizeParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<ProviderAuthAuthorization, OpencodeError> {
+        self.client
+            .post(&encode_path!("/provider/{}/oauth/authorize", provider_id), Some(params), options)
+            .await
+    }
+
+    /// Complete an OAuth authorization and store the resulting credentials
+    /// (`POST /provider/{providerID}/oauth/callback`).
+    pub async fn oauth_callback(
+        &self,
+        provider_id: &str,
+        params: &ProviderOauthCallbackParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<ProviderOauthCallbackResponse, OpencodeError> {
+        self.client
+            .post(&encode_path!("/provider/{}/oauth/callback", provider_id), Some(params), options)
+            .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn provider_list_response_deserialize() {
+        let raw = json!({
+            "all": [{
+                "id": "anthropic",
+                "name": "Anthropic",
+                "env": ["ANTHROPIC_API_KEY"],
+                "npm": "@ai-sdk/anthropic",
+                "models": {
+                    "claude-sonnet-4": {
+                        "id": "claude-sonnet-4",
+                        "name": "Claude Sonnet 4",
+                        "release_date": "2025-05-22",
+                        "attachment": true,
+                        "reasoning": true,
+                        "temperature": true,
+                        "tool_call": true,
+                        "interleaved": { "field": "reasoning_details" },
+                        "cost": {
+                            "input": 3.0,
+                            "output": 15.0,
+                            "cache_read": 0.3,
+                            "context_over_200k": { "input": 6.0, "output": 22.5 }
+                        },
+                        "limit": { "context": 200_000.0, "output": 64_000.0 },
+                        "modalities": { "input": ["text", "image", "pdf"], "output": ["text"] },
+                        "options": {}
+                    }
+                }
+            }],
+            "default": { "anthropic": "claude-sonnet-4" },
+            "connected": ["anthropic"]
+        });
+        let resp: ProviderListResponse = serde_json::from_value(raw.clone()).unwrap();
+        let model = &resp.all[0].models["claude-sonnet-4"];
+        assert_eq!(
+            model.interleaved,
+            Some(ProviderModelInterleaved::Field { field: "reasoning_details".into() })
+        );
+        assert_eq!(
+            model.cost.as_ref().and_then(|c| c.context_over_200k.as_ref()).map(|t| t.input),
+            Some(6.0)
+        );
+        assert_eq!(
+            model.modalities.as_ref().map(|m| m.input.clone()),
+            Some(vec![Modality::Text, Modality::Image, Modality::Pdf])
+        );
+        assert_eq!(resp.connected, vec!["anthropic"]);
+        assert_eq!(serde_json::to_value(&resp).unwrap(), raw);
+    }
+
+    #[test]
+    fn provider_model_interleaved_bool() {
+        let v: ProviderModelInterleaved = serde_json::from_value(json!(true)).unwrap();
+        assert_eq!(v, ProviderModelInterleaved::Enabled(true));
+    }
+
+    #[test]
+    fn provider_auth_response_deserialize() {
+        let raw = json!({
+            "anthropic": [
+                { "type": "oauth", "label": "Claude Pro/Max" },
+                { "type": "api", "label": "Manually enter API Key" }
+            ]
+        });
+        let resp: ProviderAuthResponse = serde_json::from_value(raw).unwrap();
+        assert_eq!(resp["anthropic"][0].kind, ProviderAuthMethodType::OAuth);
+        assert_eq!(resp["anthropic"][1].kind, ProviderAuthMethodType::Api);
+    }
+
+    #[test]
+    fn provider_auth_authorization_round_trip() {
+        let raw = json!({
+            "url": "https://claude.ai/oauth/authorize?code=true",
+            "method": "code",
+            "instructions": "Paste the authorization code here: "
+        });
+        let auth: ProviderAuthAuthorization = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(auth.method, ProviderAuthAuthorizationMethod::Code);
+        assert_eq!(serde_json::to_value(&auth).unwrap(), raw);
+    }
+
+    #[test]
+    fn provider_oauth_callback_params_serialize() {
+        let params = ProviderOauthCallbackParams { method: 0, code: None };
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({ "method": 0 }));
+
+        let params = ProviderOauthCallbackParams { method: 1, code: Some("abc#xyz".into()) };
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            json!({ "method": 1, "code": "abc#xyz" })
+        );
+    }
+}
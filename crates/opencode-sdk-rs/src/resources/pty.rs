@@ -0,0 +1,287 @@
+//! PTY resource types and methods mirroring the JS SDK's `resources/pty.ts`.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use super::event::Pty;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+    websocket::{WebSocketReader, WebSocketWriter},
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Terminal dimensions, in character cells.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PtySize {
+    /// Number of rows.
+    pub rows: u16,
+    /// Number of columns.
+    pub cols: u16,
+}
+
+/// Response type for [`PtyResource::list`].
+pub type PtyListResponse = Vec<Pty>;
+
+/// Response type for [`PtyResource::delete`].
+pub type PtyDeleteResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`PtyResource::create`].
+///
+/// Every field is optional; the server falls back to the user's shell in the
+/// project directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PtyCreateParams {
+    /// Command to run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Command arguments.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+    /// Working directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Display title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Extra environment variables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+}
+
+/// Parameters for [`PtyResource::update`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PtyUpdateParams {
+    /// New display title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// New terminal size.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<PtySize>,
+}
+
+// ---------------------------------------------------------------------------
+// Connection
+// ---------------------------------------------------------------------------
+
+/// A live, bidirectional connection to a PTY (`GET /pty/{ptyID}/connect`).
+///
+/// Obtained from [`PtyResource::connect`].  Terminal output is read with
+/// [`recv`](Self::recv); input is written with [`send`](Self::send).  To read
+/// and write concurrently (e.g. from two `tokio::select!` branches or two
+/// tasks), [`split`](Self::split) the connection into its halves.
+///
+/// Pings from the server are answered in the background, whether or not
+/// anybody is currently reading.
+#[derive(Debug)]
+pub struct PtyConnection {
+    reader: PtyReader,
+    writer: PtyWriter,
+}
+
+impl PtyConnection {
+    /// Receive the next chunk of terminal output.
+    ///
+    /// See [`PtyReader::recv`].
+    pub async fn recv(&mut self) -> Option<Result<Bytes, OpencodeError>> {
+        self.reader.recv().await
+    }
+
+    /// Write input to the terminal.
+    ///
+    /// See [`PtyWriter::send`].
+    pub async fn send(&self, input: impl AsRef<[u8]>) -> Result<(), OpencodeError> {
+        self.writer.send(input).await
+    }
+
+    /// Close the connection.  The PTY itself keeps running; use
+    /// [`PtyResource::delete`] to terminate it.
+    pub async fn close(self) -> Result<(), OpencodeError> {
+        self.writer.close().await
+    }
+
+    /// Split the connection into a reading and a writing half.
+    pub fn split(self) -> (PtyReader, PtyWriter) {
+        (self.reader, self.writer)
+    }
+}
+
+/// The output half of a [`PtyConnection`].
+///
+/// Dropping it stops reading from the connection.
+#[derive(Debug)]
+pub struct PtyReader {
+    inner: WebSocketReader,
+}
+
+impl PtyReader {
+    /// Receive the next chunk of terminal output.
+    ///
+    /// Returns `None` once the server closes the connection (e.g. after the
+    /// process exits).  This method is cancel-safe: if the future is dropped
+    /// (say, because another `tokio::select!` branch completed first), no
+    /// output is lost.
+    pub async fn recv(&mut self) -> Option<Result<Bytes, OpencodeError>> {
+        self.inner.recv().await
+    }
+}
+
+/// The input half of a [`PtyConnection`].
+///
+/// Clones write to the same connection; messages are sent in the order they
+/// are queued.
+#[derive(Debug, Clone)]
+pub struct PtyWriter {
+    inner: WebSocketWriter,
+}
+
+impl PtyWriter {
+    /// Write input to the terminal.
+    ///
+    /// Valid UTF-8 is sent as a text message, anything else as binary.  This
+    /// method is not cancel-safe: once queued, the input is written even if
+    /// the returned future is dropped before it completes.
+    pub async fn send(&self, input: impl AsRef<[u8]>) -> Result<(), OpencodeError> {
+        let input = input.as_ref();
+        match std::str::from_utf8(input) {
+            Ok(text) => self.inner.send_text(text).await,
+            Err(_) => self.inner.send_binary(input).await,
+        }
+    }
+
+    /// Close the connection.  The PTY itself keeps running; use
+    /// [`PtyResource::delete`] to terminate it.
+    pub async fn close(self) -> Result<(), OpencodeError> {
+        self.inner.close().await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/pty` endpoints.
+pub struct PtyResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> PtyResource<'a> {
+    /// Create a new `PtyResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List active PTY sessions (`GET /pty`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<PtyListResponse, OpencodeError> {
+        self.client.get("/pty", options).await
+    }
+
+    /// Create a new PTY session (`POST /pty`).
+    pub async fn create(
+        &self,
+        params: &PtyCreateParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Pty, OpencodeError> {
+        self.client.post("/pty", Some(params), options).await
+    }
+
+    /// Retrieve a PTY session (`GET /pty/{ptyID}`).
+    pub async fn get(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<Pty, OpencodeError> {
+        self.client.get(&encode_path!("/pty/{}", id), options).await
+    }
+
+    /// Update a PTY session's title or size (`PUT /pty/{ptyID}`).
+    pub async fn update(
+        &self,
+        id: &str,
+        params: &PtyUpdateParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Pty, OpencodeError> {
+        self.client.put(&encode_path!("/pty/{}", id), Some(params), options).await
+    }
+
+    /// Terminate and remove a PTY session (`DELETE /pty/{ptyID}`).
+    pub async fn delete(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<PtyDeleteResponse, OpencodeError> {
+        self.client.delete::<bool, ()>(&encode_path!("/pty/{}", id), None, options).await
+    }
+
+    /// Attach to a PTY session over WebSocket (`GET /pty/{ptyID}/connect`).
+    pub async fn connect(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<PtyConnection, OpencodeError> {
+        let (reader, writer) =
+            self.client.connect_websocket(&encode_path!("/pty/{}/connect", id), options).await?;
+        Ok(PtyConnection {
+            reader: PtyReader { inner: reader },
+            writer: PtyWriter { inner: writer },
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn pty_create_params_skip_none() {
+        let params = PtyCreateParams::default();
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({}));
+
+        let params = PtyCreateParams {
+            command: Some("bash".into()),
+            args: Some(vec!["-l".into()]),
+            cwd: Some("/tmp".into()),
+            title: Some("ci".into()),
+            env: Some(HashMap::from([("TERM".into(), "xterm".into())])),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            v,
+            json!({
+                "command": "bash",
+                "args": ["-l"],
+                "cwd": "/tmp",
+                "title": "ci",
+                "env": { "TERM": "xterm" }
+            })
+        );
+    }
+
+    #[test]
+    fn pty_update_params_serialize() {
+        let params = PtyUpdateParams { title: None, size: Some(PtySize { rows: 24, cols: 80 }) };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "size": { "rows": 24, "cols": 80 } }));
+        let back: PtyUpdateParams = serde_json::from_value(v).unwrap();
+        assert_eq!(params, back);
+    }
+}
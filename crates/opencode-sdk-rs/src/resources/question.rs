@@ -0,0 +1,205 @@
+//! Question resource types and methods mirroring the JS SDK's `resources/question.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    url::encode_path,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A selectable choice offered by a [`QuestionInfo`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuestionOption {
+    /// Display text (1-5 words).
+    pub label: String,
+    /// Explanation of the choice.
+    pub description: String,
+}
+
+/// A single question asked by the assistant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuestionInfo {
+    /// The complete question.
+    pub question: String,
+    /// Very short label (max 30 chars).
+    pub header: String,
+    /// Available choices.
+    pub options: Vec<QuestionOption>,
+    /// Whether multiple choices may be selected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple: Option<bool>,
+    /// Whether a custom answer may be typed (server default: `true`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom: Option<bool>,
+}
+
+/// The tool call that triggered a [`QuestionRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuestionRequestTool {
+    /// The message containing the tool call.
+    #[serde(rename = "messageID")]
+    pub message_id: String,
+    /// The tool call identifier.
+    #[serde(rename = "callID")]
+    pub call_id: String,
+}
+
+/// A pending question request raised by the assistant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuestionRequest {
+    /// Unique request identifier (`que…`).
+    pub id: String,
+    /// The session that raised the request.
+    #[serde(rename = "sessionID")]
+    pub session_id: String,
+    /// Questions to ask.
+    pub questions: Vec<QuestionInfo>,
+    /// The tool call that triggered the request, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<QuestionRequestTool>,
+}
+
+/// An answer to a single question: the selected (or custom) labels.
+pub type QuestionAnswer = Vec<String>;
+
+/// Response type for [`QuestionResource::list`].
+pub type QuestionListResponse = Vec<QuestionRequest>;
+
+/// Response type for [`QuestionResource::reply`].
+pub type QuestionReplyResponse = bool;
+
+/// Response type for [`QuestionResource::reject`].
+pub type QuestionRejectResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`QuestionResource::reply`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QuestionReplyParams {
+    /// Answers in the same order as [`QuestionRequest::questions`].
+    pub answers: Vec<QuestionAnswer>,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/question` endpoints.
+pub struct QuestionResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> QuestionResource<'a> {
+    /// Create a new `QuestionResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List pending question requests across all sessions (`GET /question`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<QuestionListResponse, OpencodeError> {
+        self.client.get("/question", options).await
+    }
+
+    /// Answer a question request (`POST /question/{requestID}/reply`).
+    pub async fn reply(
+        &self,
+        request_id: &str,
+        params: &QuestionReplyParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<QuestionReplyResponse, OpencodeError> {
+        self.client
+            .post(&encode_path!("/question/{}/reply", request_id), Some(params), options)
+            .await
+    }
+
+    /// Reject a question request (`POST /question/{requestID}/reject`).
+    pub async fn reject(
+        &self,
+        request_id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<QuestionRejectResponse, OpencodeError> {
+        self.client
+            .post::<bool, ()>(&encode_path!("/question/{}/reject", request_id), None, options)
+            .await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn question_request_round_trip() {
+        let req = QuestionRequest {
+            id: "que_001".into(),
+            session_id: "ses_001".into(),
+            questions: vec![QuestionInfo {
+                question: "Which database should we use?".into(),
+                header: "Database".into(),
+                options: vec![
+                    QuestionOption {
+                        label: "Postgres".into(),
+                        description: "Relational, battle-tested".into(),
+                    },
+                    QuestionOption {
+                        label: "SQLite".into(),
+                        description: "Embedded, zero setup".into(),
+                    },
+                ],
+                multiple: Some(false),
+                custom: None,
+            }],
+            tool: Some(QuestionRequestTool {
+                message_id: "msg_001".into(),
+                call_id: "call_001".into(),
+            }),
+        };
+        let json_str = serde_json::to_string(&req).unwrap();
+        assert!(json_str.contains(r#""sessionID":"ses_001"#));
+        assert!(!json_str.contains("custom"));
+        let back: QuestionRequest = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(req, back);
+    }
+
+    #[test]
+    fn question_request_minimal() {
+        let raw = r#"{
+            "id": "que_002",
+            "sessionID": "ses_001",
+            "questions": [{
+                "question": "Proceed?",
+                "header": "Confirm",
+                "options": [{ "label": "Yes", "description": "Continue" }]
+            }]
+        }"#;
+        let req: QuestionRequest = serde_json::from_str(raw).unwrap();
+        assert_eq!(req.questions.len(), 1);
+        assert_eq!(req.questions[0].multiple, None);
+        assert!(req.tool.is_none());
+    }
+
+    #[test]
+    fn question_reply_params_serialize() {
+        let params = QuestionReplyParams {
+            answers: vec![vec!["Postgres".into()], vec!["lint".into(), "test".into()]],
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "answers": [["Postgres"], ["lint", "test"]] }));
+    }
+}
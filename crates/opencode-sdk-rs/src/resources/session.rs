@@ -4,10 +4,11 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::shared::SessionError;
+use super::{event::Todo, lsp::LspDiagnostics, shared::SessionError};
 use crate::{
     client::{Opencode, RequestOptions},
     error::OpencodeError,
+    url::encode_path,
 };
 
 // ---------------------------------------------------------------------------
@@ -366,6 +367,16 @@ pub enum Message {
     Assistant(Box<AssistantMessage>),
 }
 
+impl Message {
+    /// The session this message belongs to.
+    pub fn session_id(&self) -> &str {
+        match self {
+            Self::User(message) => &message.session_id,
+            Self::Assistant(message) => &message.session_id,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Parts
 // ---------------------------------------------------------------------------
@@ -700,6 +711,57 @@ pub enum Part {
     Unknown,
 }
 
+impl Part {
+    /// The session this part belongs to, if known.
+    pub fn session_id(&self) -> Option<&str> {
+        let session_id = match self {
+            Self::Text(part) => &part.session_id,
+            Self::File(part) => &part.session_id,
+            Self::Tool(part) => &part.session_id,
+            Self::StepStart(part) => &part.session_id,
+            Self::StepFinish(part) => &part.session_id,
+            Self::Snapshot(part) => &part.session_id,
+            Self::Patch(part) => &part.session_id,
+            Self::Subtask(part) => &part.session_id,
+            Self::Reasoning(part) => &part.session_id,
+            Self::Agent(part) => &part.session_id,
+            Self::Compaction(part) => &part.session_id,
+            Self::Retry(part) => &part.session_id,
+            Self::Unknown => return None,
+        };
+        Some(session_id)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session Status
+// ---------------------------------------------------------------------------
+
+/// Processing status of a session — discriminated by `type`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum SessionStatus {
+    /// The session is not processing anything.
+    #[serde(rename = "idle")]
+    Idle,
+    /// The session is generating a response or running tools.
+    #[serde(rename = "busy")]
+    Busy,
+    /// A provider request failed and will be retried.
+    #[serde(rename = "retry")]
+    Retry {
+        /// The retry attempt number.
+        attempt: f64,
+        /// Description of the error that triggered the retry.
+        message: String,
+        /// Epoch timestamp (milliseconds) of the next attempt.
+        next: f64,
+    },
+    /// Any unknown status returned by newer server versions.
+    #[serde(other)]
+    Unknown,
+}
+
 // ---------------------------------------------------------------------------
 // Tool States
 // ---------------------------------------------------------------------------
@@ -746,6 +808,20 @@ pub struct ToolStateCompleted {
     pub title: String,
 }
 
+impl ToolStateCompleted {
+    /// Diagnostics the server attached to this tool result under
+    /// `metadata.diagnostics`.
+    ///
+    /// Edit and write tools report the language-server diagnostics for the
+    /// files they touched; other tools return an empty map.  Fails if the
+    /// attached value does not have the expected shape.
+    pub fn diagnostics(&self) -> Result<LspDiagnostics, serde_json::Error> {
+        self.metadata
+            .get("diagnostics")
+            .map_or_else(|| Ok(LspDiagnostics::new()), LspDiagnostics::deserialize)
+    }
+}
+
 /// Timing for [`ToolStateCompleted`].
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolStateCompletedTime {
@@ -791,6 +867,15 @@ pub enum ToolState {
     /// The tool finished with an error.
     #[serde(rename = "error")]
     Error(ToolStateError),
+    /// A state this SDK version cannot decode, kept verbatim.
+    #[serde(untagged)]
+    Unknown {
+        /// The state's `status` tag.
+        status: String,
+        /// The remaining raw fields.
+        #[serde(flatten)]
+        fields: serde_json::Map<String, serde_json::Value>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -850,7 +935,7 @@ pub struct SymbolSourcePosition {
     pub line: u64,
 }
 
-/// Source of a file part — either a file or a symbol.
+/// Source of a file part — a file, a symbol, or an unrecognised kind.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 pub enum FilePartSource {
@@ -860,6 +945,16 @@ pub enum FilePartSource {
     /// A symbol source (function, class, etc.).
     #[serde(rename = "symbol")]
     Symbol(SymbolSource),
+    /// A source kind this SDK version cannot decode, kept verbatim.
+    #[serde(untagged)]
+    Unknown {
+        /// The source's `type` tag.
+        #[serde(rename = "type")]
+        source_type: String,
+        /// The remaining raw fields.
+        #[serde(flatten)]
+        fields: serde_json::Map<String, serde_json::Value>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -998,6 +1093,19 @@ pub type SessionInitResponse = bool;
 /// Response type for summarising a session.
 pub type SessionSummarizeResponse = bool;
 
+/// Response type for [`SessionResource::children`].
+pub type SessionChildrenResponse = Vec<Session>;
+
+/// Response type for [`SessionResource::diff`].
+pub type SessionDiffResponse = Vec<FileDiff>;
+
+/// Response type for [`SessionResource::todo`].
+pub type SessionTodoResponse = Vec<Todo>;
+
+/// Response type for [`SessionResource::status`]: the status of every
+/// non-idle session, keyed by session ID.
+pub type SessionStatusResponse = HashMap<String, SessionStatus>;
+
 // ---------------------------------------------------------------------------
 // Param Types
 // ---------------------------------------------------------------------------
@@ -1083,6 +1191,85 @@ pub struct SessionSummarizeParams {
     pub provider_id: String,
 }
 
+/// Parameters for running a slash command (`POST /session/{id}/command`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionCommandParams {
+    /// Command name, without the leading `/`.
+    pub command: String,
+    /// Raw argument string substituted into the command template.
+    pub arguments: String,
+    /// Optional agent override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Optional model override, as `provider/model`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Optional message identifier for the generated user message.
+    #[serde(rename = "messageID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    /// Optional variant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// Optional file attachments; only [`PartInput::File`] is accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parts: Option<Vec<PartInput>>,
+}
+
+/// Parameters for running a shell command (`POST /session/{id}/shell`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionShellParams {
+    /// Shell command line to run.
+    pub command: String,
+    /// Agent the command is attributed to.
+    pub agent: String,
+    /// Optional model selection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<SessionChatModel>,
+}
+
+/// Timestamps that can be changed with [`SessionResource::update`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionUpdateTime {
+    /// Epoch timestamp at which the session was archived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<f64>,
+}
+
+/// Parameters for updating a session (`PATCH /session/{id}`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionUpdateParams {
+    /// New session title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// New permission rules for the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permission: Option<PermissionRuleset>,
+    /// Timestamp changes, e.g. archiving.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<SessionUpdateTime>,
+}
+
+/// Parameters for forking a session (`POST /session/{id}/fork`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionForkParams {
+    /// Message to fork at; messages after it are not copied.  Forks the whole
+    /// session when omitted.
+    #[serde(rename = "messageID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
+/// Query parameters for a session diff (`GET /session/{id}/diff`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionDiffParams {
+    /// Restrict the diff to the changes made in response to this user
+    /// message.
+    #[serde(rename = "messageID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // SessionResource
 // ---------------------------------------------------------------------------
@@ -1111,13 +1298,33 @@ impl<'a> SessionResource<'a> {
         self.client.get("/session", options).await
     }
 
+    /// Retrieve a single session (`GET /session/{id}`).
+    pub async fn get(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<Session, OpencodeError> {
+        self.client.get(&encode_path!("/session/{}", id), options).await
+    }
+
+    /// Update a session's title, permissions or archive time
+    /// (`PATCH /session/{id}`).
+    pub async fn update(
+        &self,
+        id: &str,
+        params: &SessionUpdateParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Session, OpencodeError> {
+        self.client.patch(&encode_path!("/session/{}", id), Some(params), options).await
+    }
+
     /// Delete a session (`DELETE /session/{id}`).
     pub async fn delete(
         &self,
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<SessionDeleteResponse, OpencodeError> {
-        self.client.delete::<bool, ()>(&format!("/session/{id}"), None, options).await
+        self.client.delete::<bool, ()>(&encode_path!("/session/{}", id), None, options).await
     }
 
     /// Abort a running session (`POST /session/{id}/abort`).
@@ -1126,7 +1333,7 @@ impl<'a> SessionResource<'a> {
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<SessionAbortResponse, OpencodeError> {
-        self.client.post::<bool, ()>(&format!("/session/{id}/abort"), None, options).await
+        self.client.post::<bool, ()>(&encode_path!("/session/{}/abort", id), None, options).await
     }
 
     /// Send a chat message (`POST /session/{id}/message`).
@@ -1136,7 +1343,68 @@ impl<'a> SessionResource<'a> {
         params: &SessionChatParams,
         options: Option<&RequestOptions>,
     ) -> Result<SessionMessagesResponseItem, OpencodeError> {
-        self.client.post(&format!("/session/{id}/message"), Some(params), options).await
+        let result =
+            self.client.post(&encode_path!("/session/{}/message", id), Some(params), options).await;
+        self.abort_if_cancelled(id, options, result).await
+    }
+
+    /// Send a chat message without waiting for the reply
+    /// (`POST /session/{id}/prompt_async`).
+    ///
+    /// Returns once the server has accepted the prompt, so long agent runs
+    /// are not bound by the request timeout.  Follow progress on the event
+    /// stream or with [`messages`](Self::messages).
+    pub async fn prompt_async(
+        &self,
+        id: &str,
+        params: &SessionChatParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<(), OpencodeError> {
+        self.client.post(&encode_path!("/session/{}/prompt_async", id), Some(params), options).await
+    }
+
+    /// Run a slash command in a session (`POST /session/{id}/command`).
+    pub async fn command(
+        &self,
+        id: &str,
+        params: &SessionCommandParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<SessionMessagesResponseItem, OpencodeError> {
+        let result =
+            self.client.post(&encode_path!("/session/{}/command", id), Some(params), options).await;
+        self.abort_if_cancelled(id, options, result).await
+    }
+
+    /// Run a shell command in a session (`POST /session/{id}/shell`).
+    pub async fn shell(
+        &self,
+        id: &str,
+        params: &SessionShellParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<AssistantMessage, OpencodeError> {
+        let result =
+            self.client.post(&encode_path!("/session/{}/shell", id), Some(params), options).await;
+        self.abort_if_cancelled(id, options, result).await
+    }
+
+    /// After a cancelled request, abort the session on the server too when
+    /// [`RequestOptions::abort_on_cancel`] is set.  Failing to abort is only
+    /// logged; the caller still gets [`OpencodeError::UserAbort`].
+    async fn abort_if_cancelled<T>(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+        result: Result<T, OpencodeError>,
+    ) -> Result<T, OpencodeError> {
+        if let (Err(OpencodeError::UserAbort), Some(options)) = (&result, options) &&
+            options.abort_on_cancel
+        {
+            let abort_options = RequestOptions { cancel: None, ..options.clone() };
+            if let Err(err) = self.abort(id, Some(&abort_options)).await {
+                tracing::warn!(session = id, error = %err, "failed to abort cancelled session");
+            }
+        }
+        result
     }
 
     /// Initialise a session (`POST /session/{id}/init`).
@@ -1146,7 +1414,7 @@ impl<'a> SessionResource<'a> {
         params: &SessionInitParams,
         options: Option<&RequestOptions>,
     ) -> Result<SessionInitResponse, OpencodeError> {
-        self.client.post(&format!("/session/{id}/init"), Some(params), options).await
+        self.client.post(&encode_path!("/session/{}/init", id), Some(params), options).await
     }
 
     /// List messages in a session (`GET /session/{id}/message`).
@@ -1155,7 +1423,7 @@ impl<'a> SessionResource<'a> {
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<SessionMessagesResponse, OpencodeError> {
-        self.client.get(&format!("/session/{id}/message"), options).await
+        self.client.get(&encode_path!("/session/{}/message", id), options).await
     }
 
     /// Revert a session to a previous state (`POST /session/{id}/revert`).
@@ -1165,7 +1433,7 @@ impl<'a> SessionResource<'a> {
         params: &SessionRevertParams,
         options: Option<&RequestOptions>,
     ) -> Result<Session, OpencodeError> {
-        self.client.post(&format!("/session/{id}/revert"), Some(params), options).await
+        self.client.post(&encode_path!("/session/{}/revert", id), Some(params), options).await
     }
 
     /// Share a session (`POST /session/{id}/share`).
@@ -1174,7 +1442,7 @@ impl<'a> SessionResource<'a> {
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<Session, OpencodeError> {
-        self.client.post::<Session, ()>(&format!("/session/{id}/share"), None, options).await
+        self.client.post::<Session, ()>(&encode_path!("/session/{}/share", id), None, options).await
     }
 
     /// Summarise a session (`POST /session/{id}/summarize`).
@@ -1184,7 +1452,7 @@ impl<'a> SessionResource<'a> {
         params: &SessionSummarizeParams,
         options: Option<&RequestOptions>,
     ) -> Result<SessionSummarizeResponse, OpencodeError> {
-        self.client.post(&format!("/session/{id}/summarize"), Some(params), options).await
+        self.client.post(&encode_path!("/session/{}/summarize", id), Some(params), options).await
     }
 
     /// Unrevert a session (`POST /session/{id}/unrevert`).
@@ -1193,7 +1461,9 @@ impl<'a> SessionResource<'a> {
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<Session, OpencodeError> {
-        self.client.post::<Session, ()>(&format!("/session/{id}/unrevert"), None, options).await
+        self.client
+            .post::<Session, ()>(&encode_path!("/session/{}/unrevert", id), None, options)
+            .await
     }
 
     /// Unshare a session (`DELETE /session/{id}/share`).
@@ -1202,7 +1472,55 @@ impl<'a> SessionResource<'a> {
         id: &str,
         options: Option<&RequestOptions>,
     ) -> Result<Session, OpencodeError> {
-        self.client.delete::<Session, ()>(&format!("/session/{id}/share"), None, options).await
+        self.client
+            .delete::<Session, ()>(&encode_path!("/session/{}/share", id), None, options)
+            .await
+    }
+
+    /// List the child sessions of a session (`GET /session/{id}/children`).
+    pub async fn children(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<SessionChildrenResponse, OpencodeError> {
+        self.client.get(&encode_path!("/session/{}/children", id), options).await
+    }
+
+    /// Fork a session into a new one (`POST /session/{id}/fork`).
+    pub async fn fork(
+        &self,
+        id: &str,
+        params: &SessionForkParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Session, OpencodeError> {
+        self.client.post(&encode_path!("/session/{}/fork", id), Some(params), options).await
+    }
+
+    /// Get the file changes made in a session (`GET /session/{id}/diff`).
+    pub async fn diff(
+        &self,
+        id: &str,
+        params: Option<&SessionDiffParams>,
+        options: Option<&RequestOptions>,
+    ) -> Result<SessionDiffResponse, OpencodeError> {
+        self.client.get_with_query(&encode_path!("/session/{}/diff", id), params, options).await
+    }
+
+    /// Get a session's todo list (`GET /session/{id}/todo`).
+    pub async fn todo(
+        &self,
+        id: &str,
+        options: Option<&RequestOptions>,
+    ) -> Result<SessionTodoResponse, OpencodeError> {
+        self.client.get(&encode_path!("/session/{}/todo", id), options).await
+    }
+
+    /// Get the status of all sessions (`GET /session/status`).
+    pub async fn status(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<SessionStatusResponse, OpencodeError> {
+        self.client.get("/session/status", options).await
     }
 }
 
@@ -1617,6 +1935,40 @@ mod tests {
         assert_eq!(state, back);
     }
 
+    #[test]
+    fn tool_state_completed_diagnostics() {
+        let mut state = ToolStateCompleted {
+            input: HashMap::new(),
+            metadata: HashMap::from([(
+                "diagnostics".into(),
+                json!({
+                    "/repo/src/main.ts": [{
+                        "range": {
+                            "start": { "line": 0, "character": 6 },
+                            "end": { "line": 0, "character": 9 }
+                        },
+                        "severity": 2,
+                        "message": "'foo' is declared but never used."
+                    }]
+                }),
+            )]),
+            output: String::new(),
+            time: ToolStateCompletedTime { end: 2.0, start: 1.0 },
+            title: "src/main.ts".into(),
+        };
+        let diagnostics = state.diagnostics().unwrap();
+        assert_eq!(
+            diagnostics["/repo/src/main.ts"][0].severity,
+            Some(crate::resources::lsp::LspDiagnosticSeverity::Warning)
+        );
+
+        state.metadata.insert("diagnostics".into(), json!(["not", "a", "map"]));
+        assert!(state.diagnostics().is_err());
+
+        state.metadata.clear();
+        assert!(state.diagnostics().unwrap().is_empty());
+    }
+
     #[test]
     fn tool_state_error() {
         let state = ToolState::Error(ToolStateError {
@@ -1631,6 +1983,20 @@ mod tests {
         assert_eq!(state, back);
     }
 
+    #[test]
+    fn tool_state_unknown_status() {
+        let raw = json!({ "status": "awaiting_approval", "input": { "cmd": "rm -rf /" } });
+        let state: ToolState = serde_json::from_value(raw.clone()).unwrap();
+        match &state {
+            ToolState::Unknown { status, fields } => {
+                assert_eq!(status, "awaiting_approval");
+                assert_eq!(fields["input"]["cmd"], "rm -rf /");
+            }
+            other => panic!("expected Unknown, got {other:?}"),
+        }
+        assert_eq!(serde_json::to_value(&state).unwrap(), raw);
+    }
+
     // -- FilePartSource enum --
 
     #[test]
@@ -1669,6 +2035,23 @@ mod tests {
         assert_eq!(src, back);
     }
 
+    #[test]
+    fn file_part_source_unknown_variant() {
+        let raw = json!({
+            "type": "resource",
+            "clientName": "docs",
+            "uri": "docs://guide",
+            "text": { "start": 0, "end": 5, "value": "@docs" }
+        });
+        let src: FilePartSource = serde_json::from_value(raw.clone()).unwrap();
+        assert!(matches!(
+            src,
+            FilePartSource::Unknown { ref source_type, ref fields }
+                if source_type == "resource" && fields["uri"] == "docs://guide"
+        ));
+        assert_eq!(serde_json::to_value(&src).unwrap(), raw);
+    }
+
     // -- SessionChatParams --
 
     #[test]
@@ -1848,6 +2231,82 @@ mod tests {
         assert_eq!(params, back);
     }
 
+    #[test]
+    fn session_update_params_serialize() {
+        let params = SessionUpdateParams {
+            title: Some("Renamed".into()),
+            time: Some(SessionUpdateTime { archived: Some(1_700_000_000_000.0) }),
+            ..SessionUpdateParams::default()
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "title": "Renamed", "time": { "archived": 1_700_000_000_000.0 } }));
+    }
+
+    #[test]
+    fn session_fork_params_serialize() {
+        assert_eq!(serde_json::to_value(SessionForkParams::default()).unwrap(), json!({}));
+        let params = SessionForkParams { message_id: Some("msg_001".into()) };
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({ "messageID": "msg_001" }));
+    }
+
+    #[test]
+    fn session_command_params_serialize() {
+        let params = SessionCommandParams {
+            command: "review".into(),
+            arguments: "src/lib.rs".into(),
+            agent: Some("plan".into()),
+            model: None,
+            message_id: None,
+            variant: None,
+            parts: None,
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "command": "review", "arguments": "src/lib.rs", "agent": "plan" }));
+    }
+
+    #[test]
+    fn session_shell_params_serialize() {
+        let params = SessionShellParams {
+            command: "cargo test".into(),
+            agent: "build".into(),
+            model: Some(SessionChatModel {
+                provider_id: "anthropic".into(),
+                model_id: "claude-sonnet-4".into(),
+            }),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v["model"]["providerID"], "anthropic");
+        assert_eq!(v["agent"], "build");
+    }
+
+    #[test]
+    fn session_status_variants() {
+        let idle: SessionStatus = serde_json::from_value(json!({ "type": "idle" })).unwrap();
+        assert_eq!(idle, SessionStatus::Idle);
+
+        let raw = json!({
+            "type": "retry",
+            "attempt": 2,
+            "message": "Rate limited",
+            "next": 1_700_000_005_000.0
+        });
+        let retry: SessionStatus = serde_json::from_value(raw.clone()).unwrap();
+        assert!(
+            matches!(retry, SessionStatus::Retry { ref message, .. } if message == "Rate limited")
+        );
+        assert_eq!(serde_json::to_value(&retry).unwrap()["attempt"], 2.0);
+
+        let busy = serde_json::to_value(SessionStatus::Busy).unwrap();
+        assert_eq!(busy, json!({ "type": "busy" }));
+    }
+
+    #[test]
+    fn session_status_unknown_variant() {
+        let status: SessionStatus =
+            serde_json::from_value(json!({ "type": "compacting", "progress": 0.5 })).unwrap();
+        assert_eq!(status, SessionStatus::Unknown);
+    }
+
     // -- Deserialization from JS-compatible JSON --
 
     #[test]
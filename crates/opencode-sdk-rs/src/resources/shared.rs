@@ -170,6 +170,18 @@ pub enum SessionError {
         /// Structured error data.
         data: ApiErrorData,
     },
+    /// An error kind this SDK version cannot decode, kept verbatim.
+    ///
+    /// Not to be confused with [`SessionError::UnknownError`], which is an
+    /// error kind reported by the server.
+    #[serde(untagged)]
+    Unknown {
+        /// The error's `name` tag.
+        name: String,
+        /// The raw error data.
+        #[serde(default)]
+        data: serde_json::Value,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -412,6 +424,20 @@ mod tests {
         assert_eq!(err, back);
     }
 
+    #[test]
+    fn session_error_unrecognised_name() {
+        let raw = json!({ "name": "QuotaExceededError", "data": { "resetAt": 1_700_000_000 } });
+        let err: SessionError = serde_json::from_value(raw.clone()).unwrap();
+        match &err {
+            SessionError::Unknown { name, data } => {
+                assert_eq!(name, "QuotaExceededError");
+                assert_eq!(data["resetAt"], 1_700_000_000);
+            }
+            other => panic!("expected Unknown, got {other:?}"),
+        }
+        assert_eq!(serde_json::to_value(&err).unwrap(), raw);
+    }
+
     #[test]
     fn session_error_output_length_round_trip_with_data() {
         let err = SessionError::MessageOutputLengthError {
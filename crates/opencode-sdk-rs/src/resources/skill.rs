@@ -0,0 +1,74 @@
+//! Skill resource types and methods mirroring the JS SDK's `resources/skill.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A skill (`SKILL.md`) available to agents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillInfo {
+    /// Skill name.
+    pub name: String,
+    /// Description used by agents to decide when to load the skill.
+    pub description: String,
+    /// Path of the skill file.
+    pub location: String,
+    /// Skill body (Markdown).
+    pub content: String,
+}
+
+/// Response type for [`SkillResource::list`].
+pub type SkillListResponse = Vec<SkillInfo>;
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/skill` endpoint.
+pub struct SkillResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> SkillResource<'a> {
+    /// Create a new `SkillResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// List all available skills (`GET /skill`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<SkillListResponse, OpencodeError> {
+        self.client.get("/skill", options).await
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_info_round_trip() {
+        let skill = SkillInfo {
+            name: "release-notes".into(),
+            description: "Write release notes from merged PRs".into(),
+            location: "/repo/.opencode/skill/release-notes/SKILL.md".into(),
+            content: "# Release notes\n".into(),
+        };
+        let json_str = serde_json::to_string(&skill).unwrap();
+        let back: SkillInfo = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(skill, back);
+    }
+}
@@ -2,7 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{client::Opencode, error::OpencodeError};
+use super::event::ToastVariant;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
 
 // ---------------------------------------------------------------------------
 // Type aliases
@@ -14,6 +18,29 @@ pub type TuiAppendPromptResponse = bool;
 /// Response type for [`TuiResource::open_help`].
 pub type TuiOpenHelpResponse = bool;
 
+/// Response type for the TUI action endpoints that only acknowledge the
+/// request, such as [`TuiResource::submit_prompt`].
+pub type TuiActionResponse = bool;
+
+/// Response type for [`TuiResource::control_response`].
+pub type TuiControlResponseResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A request queued for an external TUI controller.
+///
+/// Returned by [`TuiResource::control_next`]; answer it with
+/// [`TuiResource::control_response`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TuiControlRequest {
+    /// Server route that produced the request, e.g. `/tui/open-help`.
+    pub path: String,
+    /// Request body forwarded to the controller.
+    pub body: serde_json::Value,
+}
+
 // ---------------------------------------------------------------------------
 // Params
 // ---------------------------------------------------------------------------
@@ -25,6 +52,36 @@ pub struct TuiAppendPromptParams {
     pub text: String,
 }
 
+/// Parameters for [`TuiResource::execute_command`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TuiExecuteCommandParams {
+    /// TUI command name, e.g. `agent_cycle` or `session_new`.
+    pub command: String,
+}
+
+/// Parameters for [`TuiResource::show_toast`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TuiShowToastParams {
+    /// Optional title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Toast message.
+    pub message: String,
+    /// Toast variant.
+    pub variant: ToastVariant,
+    /// How long to show the toast, in milliseconds (server default: 5000).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+/// Parameters for [`TuiResource::select_session`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TuiSelectSessionParams {
+    /// Session to navigate to.
+    #[serde(rename = "sessionID")]
+    pub session_id: String,
+}
+
 // ---------------------------------------------------------------------------
 // Resource
 // ---------------------------------------------------------------------------
@@ -49,12 +106,147 @@ impl<'a> TuiResource<'a> {
         self.client.post("/tui/append-prompt", Some(params), None).await
     }
 
+    /// Submit the current TUI prompt.
+    ///
+    /// `POST /tui/submit-prompt`
+    pub async fn submit_prompt(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post::<TuiActionResponse, ()>("/tui/submit-prompt", None, options).await
+    }
+
+    /// Clear the TUI prompt.
+    ///
+    /// `POST /tui/clear-prompt`
+    pub async fn clear_prompt(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post::<TuiActionResponse, ()>("/tui/clear-prompt", None, options).await
+    }
+
+    /// Execute a TUI command.
+    ///
+    /// `POST /tui/execute-command`
+    pub async fn execute_command(
+        &self,
+        params: &TuiExecuteCommandParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post("/tui/execute-command", Some(params), options).await
+    }
+
+    /// Show a toast notification in the TUI.
+    ///
+    /// `POST /tui/show-toast`
+    pub async fn show_toast(
+        &self,
+        params: &TuiShowToastParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post("/tui/show-toast", Some(params), options).await
+    }
+
     /// Open the TUI help panel.
     ///
     /// `POST /tui/open-help`
     pub async fn open_help(&self) -> Result<TuiOpenHelpResponse, OpencodeError> {
         self.client.post::<TuiOpenHelpResponse, ()>("/tui/open-help", None, None).await
     }
+
+    /// Open the TUI session picker.
+    ///
+    /// `POST /tui/open-sessions`
+    pub async fn open_sessions(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post::<TuiActionResponse, ()>("/tui/open-sessions", None, options).await
+    }
+
+    /// Open the TUI theme picker.
+    ///
+    /// `POST /tui/open-themes`
+    pub async fn open_themes(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post::<TuiActionResponse, ()>("/tui/open-themes", None, options).await
+    }
+
+    /// Open the TUI model picker.
+    ///
+    /// `POST /tui/open-models`
+    pub async fn open_models(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post::<TuiActionResponse, ()>("/tui/open-models", None, options).await
+    }
+
+    /// Navigate the TUI to a session.
+    ///
+    /// `POST /tui/select-session`
+    pub async fn select_session(
+        &self,
+        params: &TuiSelectSessionParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiActionResponse, OpencodeError> {
+        self.client.post("/tui/select-session", Some(params), options).await
+    }
+
+    /// Wait for the next request queued for an external TUI controller.
+    ///
+    /// `GET /tui/control/next`
+    ///
+    /// This is a long poll: the server holds the request open until
+    /// something is queued, so pass a [`RequestOptions::timeout`] longer than
+    /// the client default and call again after an
+    /// [`OpencodeError::Timeout`].  Timeouts are otherwise retried, which
+    /// would hold every idle poll open for several timeouts, so retries are
+    /// disabled unless [`RequestOptions::max_retries`] is set.
+    ///
+    /// ```rust,no_run
+    /// # async fn run(client: opencode_sdk_rs::Opencode) -> Result<(), opencode_sdk_rs::OpencodeError> {
+    /// use std::time::Duration;
+    ///
+    /// use opencode_sdk_rs::{OpencodeError, RequestOptions};
+    ///
+    /// let options = RequestOptions { timeout: Some(Duration::from_secs(300)), ..Default::default() };
+    /// loop {
+    ///     let request = match client.tui().control_next(Some(&options)).await {
+    ///         Ok(request) => request,
+    ///         Err(OpencodeError::Timeout) => continue,
+    ///         Err(err) => return Err(err),
+    ///     };
+    ///     println!("TUI asked for {}", request.path);
+    ///     client.tui().control_response(&serde_json::json!(true), None).await?;
+    /// }
+    /// # }
+    /// ```
+    pub async fn control_next(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiControlRequest, OpencodeError> {
+        let options = RequestOptions {
+            max_retries: Some(options.and_then(|o| o.max_retries).unwrap_or(0)),
+            ..options.cloned().unwrap_or_default()
+        };
+        self.client.get("/tui/control/next", Some(&options)).await
+    }
+
+    /// Answer the request most recently returned by
+    /// [`control_next`](Self::control_next).
+    ///
+    /// `POST /tui/control/response`
+    pub async fn control_response<B: Serialize + Sync>(
+        &self,
+        body: &B,
+        options: Option<&RequestOptions>,
+    ) -> Result<TuiControlResponseResponse, OpencodeError> {
+        self.client.post("/tui/control/response", Some(body), options).await
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -63,6 +255,8 @@ impl<'a> TuiResource<'a> {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
+
     use super::*;
 
     #[test]
@@ -73,4 +267,33 @@ mod tests {
         let back: TuiAppendPromptParams = serde_json::from_str(&json_str).unwrap();
         assert_eq!(params, back);
     }
+
+    #[test]
+    fn tui_show_toast_params_serialize() {
+        let params = TuiShowToastParams {
+            title: None,
+            message: "Build finished".into(),
+            variant: ToastVariant::Success,
+            duration: Some(3000),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            v,
+            json!({ "message": "Build finished", "variant": "success", "duration": 3000 })
+        );
+    }
+
+    #[test]
+    fn tui_select_session_params_serialize() {
+        let params = TuiSelectSessionParams { session_id: "ses_001".into() };
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({ "sessionID": "ses_001" }));
+    }
+
+    #[test]
+    fn tui_control_request_deserialize() {
+        let raw = json!({ "path": "/tui/append-prompt", "body": { "text": "hi" } });
+        let request: TuiControlRequest = serde_json::from_value(raw).unwrap();
+        assert_eq!(request.path, "/tui/append-prompt");
+        assert_eq!(request.body["text"], "hi");
+    }
 }
@@ -0,0 +1,43 @@
+//! VCS resource types and methods mirroring the JS SDK's `resources/vcs.ts`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Version control information for the current project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VcsInfo {
+    /// Currently checked-out branch.
+    pub branch: String,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/vcs` endpoint.
+pub struct VcsResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> VcsResource<'a> {
+    /// Create a new `VcsResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Get VCS information, such as the current branch (`GET /vcs`).
+    ///
+    /// Subsequent branch changes are reported by
+    /// [`EventListResponse::VcsBranchUpdated`](super::event::EventListResponse::VcsBranchUpdated).
+    pub async fn get(&self, options: Option<&RequestOptions>) -> Result<VcsInfo, OpencodeError> {
+        self.client.get("/vcs", options).await
+    }
+}
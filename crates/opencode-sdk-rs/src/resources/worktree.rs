@@ -0,0 +1,257 @@
+//! Worktree resource types and methods mirroring the JS SDK's
+//! `resources/worktree.ts`.
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+
+use super::event::EventListResponse;
+use crate::{
+    client::{Opencode, RequestOptions},
+    error::OpencodeError,
+    streaming::SseStream,
+};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A git worktree managed by the server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Worktree {
+    /// Worktree name.
+    pub name: String,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+    /// Absolute path of the worktree directory.
+    pub directory: String,
+}
+
+/// How a worktree's setup ended, as reported on the event stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorktreeSetup {
+    /// A `worktree.ready` event arrived for the worktree.
+    Ready,
+    /// A `worktree.failed` event naming the worktree arrived.
+    Failed {
+        /// The server's failure message.
+        message: String,
+    },
+}
+
+/// Response type for [`WorktreeResource::list`]: worktree directories.
+pub type WorktreeListResponse = Vec<String>;
+
+/// Response type for [`WorktreeResource::remove`].
+pub type WorktreeRemoveResponse = bool;
+
+/// Response type for [`WorktreeResource::reset`].
+pub type WorktreeResetResponse = bool;
+
+// ---------------------------------------------------------------------------
+// Params
+// ---------------------------------------------------------------------------
+
+/// Parameters for [`WorktreeResource::create`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorktreeCreateParams {
+    /// Worktree name (generated by the server when omitted).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Extra startup script to run after the project's start command.
+    #[serde(rename = "startCommand", skip_serializing_if = "Option::is_none")]
+    pub start_command: Option<String>,
+}
+
+/// Parameters for [`WorktreeResource::remove`] and
+/// [`WorktreeResource::reset`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorktreeDirectoryParams {
+    /// Directory of the worktree to act on.
+    pub directory: String,
+}
+
+// ---------------------------------------------------------------------------
+// Resource
+// ---------------------------------------------------------------------------
+
+/// Provides access to the `/experimental/worktree` endpoints.
+pub struct WorktreeResource<'a> {
+    client: &'a Opencode,
+}
+
+impl<'a> WorktreeResource<'a> {
+    /// Create a new `WorktreeResource` bound to the given client.
+    pub(crate) const fn new(client: &'a Opencode) -> Self {
+        Self { client }
+    }
+
+    /// Create a worktree and start its setup scripts
+    /// (`POST /experimental/worktree`).
+    ///
+    /// The worktree is usable once a `worktree.ready` event arrives; see
+    /// [`create_and_await_ready`](Self::create_and_await_ready) and
+    /// [`await_ready`](Self::await_ready).
+    pub async fn create(
+        &self,
+        params: &WorktreeCreateParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<Worktree, OpencodeError> {
+        self.client.post("/experimental/worktree", Some(params), options).await
+    }
+
+    /// List worktree directories for the current project
+    /// (`GET /experimental/worktree`).
+    pub async fn list(
+        &self,
+        options: Option<&RequestOptions>,
+    ) -> Result<WorktreeListResponse, OpencodeError> {
+        self.client.get("/experimental/worktree", options).await
+    }
+
+    /// Remove a worktree and delete its branch
+    /// (`DELETE /experimental/worktree`).
+    pub async fn remove(
+        &self,
+        params: &WorktreeDirectoryParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<WorktreeRemoveResponse, OpencodeError> {
+        self.client.delete("/experimental/worktree", Some(params), options).await
+    }
+
+    /// Reset a worktree branch to the project's default branch
+    /// (`POST /experimental/worktree/reset`).
+    pub async fn reset(
+        &self,
+        params: &WorktreeDirectoryParams,
+        options: Option<&RequestOptions>,
+    ) -> Result<WorktreeResetResponse, OpencodeError> {
+        self.client.post("/experimental/worktree/reset", Some(params), options).await
+    }
+
+    /// Create a worktree and wait until its setup has finished.
+    ///
+    /// The event stream is opened before the create request is sent, so the
+    /// outcome cannot be missed.  Returns the created worktree together with
+    /// [`WorktreeSetup::Ready`] or [`WorktreeSetup::Failed`]; on failure the
+    /// worktree still exists and can be [`remove`](Self::remove)d.
+    ///
+    /// A `worktree.failed` event carries only a message, not the worktree
+    /// name, so it is attributed to this worktree only if the message
+    /// contains the name.  Failures of other worktrees are ignored, but so is
+    /// a failure of this one whose message does not name it; the wait then
+    /// ends with [`OpencodeError::Timeout`] after `timeout`.  The
+    /// cancellation token in `options` covers the whole operation.
+    pub async fn create_and_await_ready(
+        &self,
+        params: &WorktreeCreateParams,
+        timeout: Duration,
+        options: Option<&RequestOptions>,
+    ) -> Result<(Worktree, WorktreeSetup), OpencodeError> {
+        let wait = async {
+            let mut events = self.client.event().list(options).await?;
+            let worktree = self.create(params, options).await?;
+            let setup = wait_for_setup(&mut events, &worktree.name).await?;
+            Ok((worktree, setup))
+        };
+        with_timeout(timeout, wait, options).await
+    }
+
+    /// Wait until the worktree called `name` has finished its setup.
+    ///
+    /// Only events sent after the subscription is opened are seen, so for a
+    /// worktree created by this client prefer
+    /// [`create_and_await_ready`](Self::create_and_await_ready), which cannot
+    /// miss them.  Failures are attributed by name exactly as described
+    /// there, and the wait fails with [`OpencodeError::Timeout`] after
+    /// `timeout`.
+    pub async fn await_ready(
+        &self,
+        name: &str,
+        timeout: Duration,
+        options: Option<&RequestOptions>,
+    ) -> Result<WorktreeSetup, OpencodeError> {
+        let wait = async {
+            let mut events = self.client.event().list(options).await?;
+            wait_for_setup(&mut events, name).await
+        };
+        with_timeout(timeout, wait, options).await
+    }
+}
+
+/// Run `wait` under `timeout` and the cancellation token in `options`.
+async fn with_timeout<T>(
+    timeout: Duration,
+    wait: impl Future<Output = Result<T, OpencodeError>>,
+    options: Option<&RequestOptions>,
+) -> Result<T, OpencodeError> {
+    let wait = async {
+        tokio::time::timeout(timeout, Box::pin(wait)).await.map_err(|_| OpencodeError::Timeout)?
+    };
+    match options.and_then(|o| o.cancel.as_ref()) {
+        Some(token) => token.run(wait).await?,
+        None => wait.await,
+    }
+}
+
+/// Read `events` until the worktree called `name` is ready or has failed.
+async fn wait_for_setup(
+    events: &mut SseStream<EventListResponse>,
+    name: &str,
+) -> Result<WorktreeSetup, OpencodeError> {
+    while let Some(event) = std::future::poll_fn(|cx| Pin::new(&mut *events).poll_next(cx)).await {
+        match event? {
+            EventListResponse::WorktreeReady { properties } if properties.name == name => {
+                return Ok(WorktreeSetup::Ready);
+            }
+            EventListResponse::WorktreeFailed { properties }
+                if properties.message.contains(name) =>
+            {
+                return Ok(WorktreeSetup::Failed { message: properties.message });
+            }
+            _ => {}
+        }
+    }
+
+    Err(OpencodeError::Connection {
+        message: format!("event stream ended before worktree `{name}` was ready"),
+        source: None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn worktree_round_trip() {
+        let wt = Worktree {
+            name: "brave-otter".into(),
+            branch: "opencode/brave-otter".into(),
+            directory: "/home/me/.local/share/opencode/worktree/brave-otter".into(),
+        };
+        let json_str = serde_json::to_string(&wt).unwrap();
+        let back: Worktree = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(wt, back);
+    }
+
+    #[test]
+    fn worktree_create_params_serialize() {
+        let params = WorktreeCreateParams::default();
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({}));
+
+        let params = WorktreeCreateParams {
+            name: Some("agent-1".into()),
+            start_command: Some("bun install".into()),
+        };
+        let v = serde_json::to_value(&params).unwrap();
+        assert_eq!(v, json!({ "name": "agent-1", "startCommand": "bun install" }));
+    }
+}
@@ -1,27 +1,41 @@
 //! Server-Sent Events (SSE) streaming support.
 //!
-//! Provides [`SseStream`], a `futures_core::Stream` that wraps an HTTP byte
-//! stream (from `hpx::Response::bytes_stream()`) and yields typed items
-//! parsed from SSE `data:` fields.
+//! Provides [`RawSseStream`], a `futures_core::Stream` that wraps an HTTP
+//! byte stream (from `hpx::Response::bytes_stream()`) and yields undecoded
+//! [`ServerSentEvent`]s; [`SseStream`], which layers on it and yields typed
+//! items parsed from SSE `data:` fields; and [`ReconnectingSseStream`],
+//! which additionally re-opens the connection after transport errors or EOF,
+//! resuming from the last seen event ID.
 
 use std::{
+    collections::VecDeque,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
-use bytes::Bytes;
+use bytes::{Buf, Bytes, BytesMut};
 use futures_core::Stream;
-use pin_project_lite::pin_project;
 use serde::de::DeserializeOwned;
+use tokio::time::Instant;
 
-use crate::error::OpencodeError;
+use crate::{cancellation::CancellationToken, error::OpencodeError, middleware::BoxFuture};
+
+/// The raw HTTP body of an SSE response.
+pub(crate) type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, hpx::Error>> + Send>>;
+
+/// Re-opens an SSE connection, sending the given `Last-Event-ID`.
+pub(crate) type Connector = Arc<
+    dyn Fn(Option<String>) -> BoxFuture<'static, Result<ByteStream, OpencodeError>> + Send + Sync,
+>;
 
 // ---------------------------------------------------------------------------
 // ServerSentEvent
 // ---------------------------------------------------------------------------
 
 /// A single Server-Sent Event parsed from the wire format.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ServerSentEvent {
     /// The event type (from `event:` lines).
     pub event: Option<String>,
@@ -35,158 +49,582 @@ pub struct ServerSentEvent {
 // SseDecoder
 // ---------------------------------------------------------------------------
 
-/// Internal buffer that accumulates SSE lines from a byte stream
-/// and yields complete [`ServerSentEvent`]s on empty-line boundaries.
-struct SseDecoder {
-    /// Accumulates partial lines across chunk boundaries.
-    buffer: String,
+/// Incremental, byte-level parser for the `text/event-stream` format.
+///
+/// Feed it chunks exactly as they arrive with [`feed`](Self::feed) and take
+/// completed events with [`next_event`](Self::next_event).  Lines may end in
+/// `\n`, `\r\n` or a lone `\r`, and chunk boundaries may fall anywhere —
+/// including inside a multi-byte UTF-8 character or between the `\r` and
+/// `\n` of a line ending — because lines are only decoded once complete.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    /// Bytes of the current, not yet terminated line.
+    buffer: BytesMut,
+    /// How much of `buffer` is known to hold no line terminator.
+    scanned: usize,
+    /// The last line ended in `\r`; a `\n` at the start of the next chunk
+    /// completes that line ending rather than an empty line.
+    skip_lf: bool,
     /// Current `event:` value being built.
     current_event: Option<String>,
-    /// Accumulated `data:` lines for the current event.
-    current_data: Vec<String>,
+    /// Accumulated `data:` lines for the current event, joined by `\n`.
+    current_data: Option<String>,
     /// Current `id:` value being built.
     current_id: Option<String>,
+    /// Reconnection time in milliseconds from the last `retry:` line.
+    retry: Option<u64>,
+    /// Completed events not yet taken.
+    ready: VecDeque<ServerSentEvent>,
 }
 
 impl SseDecoder {
-    const fn new() -> Self {
-        Self {
-            buffer: String::new(),
-            current_event: None,
-            current_data: Vec::new(),
-            current_id: None,
+    /// Create an empty decoder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk of bytes into the decoder.
+    ///
+    /// Complete events become available from [`next_event`](Self::next_event);
+    /// a trailing partial line is kept for the next call.
+    pub fn feed(&mut self, mut chunk: &[u8]) {
+        if self.skip_lf &&
+            let Some((&first, rest)) = chunk.split_first()
+        {
+            if first == b'\n' {
+                chunk = rest;
+            }
+            self.skip_lf = false;
         }
+        self.buffer.extend_from_slice(chunk);
+
+        while let Some(offset) =
+            self.buffer[self.scanned..].iter().position(|&b| b == b'\n' || b == b'\r')
+        {
+            let end = self.scanned + offset;
+            let line = self.buffer.split_to(end);
+            let terminator = self.buffer[0];
+            self.buffer.advance(1);
+            if terminator == b'\r' {
+                match self.buffer.first() {
+                    Some(b'\n') => self.buffer.advance(1),
+                    Some(_) => {}
+                    None => self.skip_lf = true,
+                }
+            }
+            self.scanned = 0;
+            self.process_line(&line);
+        }
+        self.scanned = self.buffer.len();
     }
 
-    /// Feed a chunk of bytes into the decoder, returning any complete events.
-    fn feed(&mut self, chunk: &[u8]) -> Vec<ServerSentEvent> {
-        let text = String::from_utf8_lossy(chunk);
-        self.buffer.push_str(&text);
+    /// Signal the end of the byte stream, treating any unterminated line as
+    /// complete and dispatching the event being built.
+    pub fn finish(&mut self) {
+        if !self.buffer.is_empty() {
+            let line = self.buffer.split();
+            self.process_line(&line);
+        }
+        self.scanned = 0;
+        self.skip_lf = false;
+        self.dispatch();
+    }
 
-        let mut events = Vec::new();
+    /// Take the next completed event, if any.
+    pub fn next_event(&mut self) -> Option<ServerSentEvent> {
+        self.ready.pop_front()
+    }
 
-        // Process all complete lines (terminated by \n).
-        // Partial lines remain in `self.buffer` for the next call.
-        while let Some(newline_pos) = self.buffer.find('\n') {
-            let line = self.buffer[..newline_pos].trim_end_matches('\r').to_owned();
-            self.buffer = self.buffer[newline_pos + 1..].to_owned();
+    /// The reconnection time most recently announced with a `retry:` line.
+    #[must_use]
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry.map(Duration::from_millis)
+    }
 
-            if line.is_empty() {
-                // Empty line marks the end of an event.
-                if let Some(event) = self.emit_event() {
-                    events.push(event);
+    /// Discard any partially received line and event, keeping completed
+    /// events and the `retry:` hint.
+    pub(crate) fn reset(&mut self) {
+        self.buffer.clear();
+        self.scanned = 0;
+        self.skip_lf = false;
+        self.current_event = None;
+        self.current_data = None;
+        self.current_id = None;
+    }
+
+    /// Apply one complete line (without its terminator).
+    fn process_line(&mut self, line: &[u8]) {
+        if line.is_empty() {
+            // Empty line marks the end of an event.
+            self.dispatch();
+            return;
+        }
+
+        if line[0] == b':' {
+            // Comment line — ignore.
+            return;
+        }
+
+        // A field with no colon has an empty value.
+        let colon = line.iter().position(|&b| b == b':').unwrap_or(line.len());
+        let field = &line[..colon];
+        let value = line.get(colon + 1..).unwrap_or_default();
+        // Strip a single leading space after the colon (per SSE spec).
+        let value = value.strip_prefix(b" ").unwrap_or(value);
+
+        match field {
+            b"event" => self.current_event = Some(String::from_utf8_lossy(value).into_owned()),
+            b"data" => {
+                let value = String::from_utf8_lossy(value);
+                match self.current_data.as_mut() {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(&value);
+                    }
+                    None => self.current_data = Some(value.into_owned()),
                 }
-                continue;
             }
-
-            if line.starts_with(':') {
-                // Comment line — ignore.
-                continue;
+            b"id" => self.current_id = Some(String::from_utf8_lossy(value).into_owned()),
+            // Non-numeric values are ignored per the SSE spec.
+            b"retry" => {
+                if let Ok(ms) = std::str::from_utf8(value).unwrap_or_default().parse() {
+                    self.retry = Some(ms);
+                }
             }
+            // Unknown fields are ignored per the SSE spec.
+            _ => {}
+        }
+    }
+
+    /// Queue the current event (if any field has been set) and reset.
+    fn dispatch(&mut self) {
+        if self.current_data.is_none() && self.current_event.is_none() && self.current_id.is_none()
+        {
+            return;
+        }
+
+        self.ready.push_back(ServerSentEvent {
+            event: self.current_event.take(),
+            data: self.current_data.take().unwrap_or_default(),
+            id: self.current_id.take(),
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Reconnection
+// ---------------------------------------------------------------------------
+
+/// Back-off settings for a [`ReconnectingSseStream`].
+#[derive(Debug, Clone)]
+pub struct ReconnectOptions {
+    /// Delay before the first reconnection attempt.  A `retry:` field sent by
+    /// the server replaces it.
+    pub initial_delay: Duration,
+    /// Upper bound for the exponentially growing delay between attempts.
+    pub max_delay: Duration,
+    /// Give up after this many consecutive failed attempts; `None` retries
+    /// forever.  An attempt fails if the connection cannot be opened or
+    /// closes before sending anything.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for ReconnectOptions {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_attempts: None,
+        }
+    }
+}
+
+/// Reconnection state of an [`SseSource`].
+struct Reconnect {
+    connector: Connector,
+    options: ReconnectOptions,
+    /// Consecutive failed attempts: connections that could not be opened or
+    /// that ended before delivering any bytes.
+    failures: u32,
+    /// Abandons the back-off and connection attempt when cancelled.
+    cancel: Option<CancellationToken>,
+    /// The pending back-off sleep and connection attempt, if any.
+    attempt: Option<BoxFuture<'static, Result<ByteStream, OpencodeError>>>,
+}
 
-            let (field, value) = if let Some(colon_pos) = line.find(':') {
-                let field = &line[..colon_pos];
-                let mut value = &line[colon_pos + 1..];
-                // Strip a single leading space after the colon (per SSE spec).
-                if value.starts_with(' ') {
-                    value = &value[1..];
+impl Reconnect {
+    /// Build the next attempt: an exponentially growing, jittered delay
+    /// followed by a new connection, which fails with
+    /// [`OpencodeError::Timeout`] if it takes longer than `connect_timeout`.
+    fn schedule(
+        &self,
+        retry_hint: Option<Duration>,
+        last_event_id: Option<String>,
+        connect_timeout: Option<Duration>,
+    ) -> BoxFuture<'static, Result<ByteStream, OpencodeError>> {
+        let base = retry_hint.unwrap_or(self.options.initial_delay);
+        let delay = base
+            .saturating_mul(2_u32.saturating_pow(self.failures))
+            .min(self.options.max_delay)
+            .mul_f64(crate::client::jitter_factor());
+        tracing::debug!(
+            failures = self.failures,
+            delay_ms = delay.as_millis() as u64,
+            "reconnecting event stream"
+        );
+
+        let connector = Arc::clone(&self.connector);
+        let cancel = self.cancel.clone();
+        Box::pin(async move {
+            let attempt = async {
+                tokio::time::sleep(delay).await;
+                let connect = connector(last_event_id);
+                match connect_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, connect)
+                        .await
+                        .map_err(|_| OpencodeError::Timeout)?,
+                    None => connect.await,
                 }
-                (field.to_owned(), value.to_owned())
-            } else {
-                // Field with no value.
-                (line, String::new())
             };
-
-            match field.as_str() {
-                "event" => self.current_event = Some(value),
-                "data" => self.current_data.push(value),
-                "id" => self.current_id = Some(value),
-                // Unknown fields are ignored per the SSE spec.
-                _ => {}
+            match cancel {
+                Some(token) => token.run(attempt).await?,
+                None => attempt.await,
             }
+        })
+    }
+
+    /// Whether another attempt is allowed after `err`.
+    fn may_retry(&self, err: &OpencodeError) -> bool {
+        err.is_retryable() && self.options.max_attempts.is_none_or(|max| self.failures < max)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SseSource
+// ---------------------------------------------------------------------------
+
+/// Idle-timeout state of an [`SseSource`].
+struct IdleTimeout {
+    timeout: Duration,
+    /// Created on first poll, since a timer needs a running runtime.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+/// What an [`SseSource`] yields.
+enum SourceItem {
+    Event(ServerSentEvent),
+    Reconnected,
+}
+
+/// Turns a byte stream into [`ServerSentEvent`]s, tracking the last event ID
+/// and, when configured, reconnecting on errors and EOF.
+struct SseSource {
+    inner: ByteStream,
+    decoder: SseDecoder,
+    last_event_id: Option<String>,
+    reconnect: Option<Reconnect>,
+    idle: Option<IdleTimeout>,
+    /// When bytes (events or comments) last arrived, or the connection was
+    /// (re-)established.
+    last_activity: Instant,
+    /// Bytes have arrived on the current connection.
+    received: bool,
+    /// The connection was lost and a reconnect is due.
+    disconnected: bool,
+    done: bool,
+}
+
+impl SseSource {
+    fn new(inner: ByteStream, reconnect: Option<Reconnect>) -> Self {
+        Self {
+            inner,
+            decoder: SseDecoder::new(),
+            last_event_id: None,
+            reconnect,
+            idle: None,
+            last_activity: Instant::now(),
+            received: false,
+            disconnected: false,
+            done: false,
         }
+    }
 
-        events
+    fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.idle = Some(IdleTimeout { timeout, sleep: None });
     }
 
-    /// Emit the current event (if any data has been accumulated) and reset.
-    fn emit_event(&mut self) -> Option<ServerSentEvent> {
-        if self.current_data.is_empty() && self.current_event.is_none() && self.current_id.is_none()
+    /// Record activity on the connection, pushing back the idle deadline.
+    fn touch(&mut self) {
+        self.last_activity = Instant::now();
+        if let Some(idle) = self.idle.as_mut() &&
+            let Some(sleep) = idle.sleep.as_mut()
         {
-            return None;
+            sleep.as_mut().reset(self.last_activity + idle.timeout);
         }
+    }
 
-        let event = ServerSentEvent {
-            event: self.current_event.take(),
-            data: self.current_data.join("\n"),
-            id: self.current_id.take(),
+    /// Ready once the connection has been idle for longer than the timeout.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let Some(idle) = self.idle.as_mut() else {
+            return Poll::Pending;
         };
-        self.current_data.clear();
+        let deadline = self.last_activity + idle.timeout;
+        idle.sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)))
+            .as_mut()
+            .poll(cx)
+    }
 
-        Some(event)
+    /// Handle the loss of the connection: mark a reconnect as due if
+    /// configured, otherwise end the stream and hand `err` back to be
+    /// yielded.
+    ///
+    /// When reconnecting, an unterminated event is discarded rather than
+    /// dispatched, and a connection that ended before delivering any bytes
+    /// counts as a failed attempt.
+    fn connection_lost(&mut self, err: Option<OpencodeError>) -> Option<OpencodeError> {
+        let Some(reconnect) = self.reconnect.as_mut() else {
+            self.done = true;
+            return err;
+        };
+        if let Some(ref e) = err {
+            tracing::debug!(error = %e, "event stream interrupted");
+        }
+        if !self.received {
+            reconnect.failures += 1;
+            let err = err.unwrap_or_else(|| OpencodeError::Connection {
+                message: "event stream closed before sending any data".to_owned(),
+                source: None,
+            });
+            if !reconnect.may_retry(&err) {
+                self.done = true;
+                return Some(err);
+            }
+        }
+        self.decoder.reset();
+        self.disconnected = true;
+        None
     }
 
-    /// Flush any remaining partial event when the stream ends.
-    fn flush(&mut self) -> Option<ServerSentEvent> {
-        // If there is leftover text in the buffer, treat it as a final line.
-        if !self.buffer.is_empty() {
-            let remaining = std::mem::take(&mut self.buffer);
-            let trimmed = remaining.trim_end_matches('\r');
-            if !trimmed.is_empty() && !trimmed.starts_with(':') {
-                let (field, value) = trimmed.find(':').map_or_else(
-                    || (trimmed.to_owned(), String::new()),
-                    |colon_pos| {
-                        let field = &trimmed[..colon_pos];
-                        let mut value = &trimmed[colon_pos + 1..];
-                        if value.starts_with(' ') {
-                            value = &value[1..];
+    fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<SourceItem, OpencodeError>>> {
+        loop {
+            if let Some(event) = self.decoder.next_event() {
+                // Per the SSE spec an `id:` line, even an empty one, updates
+                // the last event ID.
+                if let Some(ref id) = event.id {
+                    self.last_event_id = (!id.is_empty()).then(|| id.clone());
+                }
+                return Poll::Ready(Some(Ok(SourceItem::Event(event))));
+            }
+
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            if self.disconnected &&
+                let Some(reconnect) = self.reconnect.as_mut()
+            {
+                // Scheduled only once pending events are drained, so the
+                // last event ID is up to date.
+                // A server that accepts the connection but never responds
+                // is as dead as an idle one.
+                let connect_timeout = self.idle.as_ref().map(|idle| idle.timeout);
+                let mut attempt = reconnect.attempt.take().unwrap_or_else(|| {
+                    reconnect.schedule(
+                        self.decoder.retry(),
+                        self.last_event_id.clone(),
+                        connect_timeout,
+                    )
+                });
+                let Poll::Ready(result) = attempt.as_mut().poll(cx) else {
+                    reconnect.attempt = Some(attempt);
+                    return Poll::Pending;
+                };
+                match result {
+                    Ok(stream) => {
+                        // `failures` is only reset once the new connection
+                        // delivers data, so one that keeps closing straight
+                        // away still backs off and hits `max_attempts`.
+                        self.inner = stream;
+                        self.received = false;
+                        self.disconnected = false;
+                        self.touch();
+                        return Poll::Ready(Some(Ok(SourceItem::Reconnected)));
+                    }
+                    Err(err) => {
+                        reconnect.failures += 1;
+                        if !reconnect.may_retry(&err) {
+                            self.done = true;
+                            return Poll::Ready(Some(Err(err)));
                         }
-                        (field.to_owned(), value.to_owned())
-                    },
-                );
-
-                match field.as_str() {
-                    "event" => self.current_event = Some(value),
-                    "data" => self.current_data.push(value),
-                    "id" => self.current_id = Some(value),
-                    _ => {}
+                        continue;
+                    }
                 }
             }
-        }
 
-        self.emit_event()
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    self.touch();
+                    if !bytes.is_empty() {
+                        self.received = true;
+                        if let Some(reconnect) = self.reconnect.as_mut() {
+                            reconnect.failures = 0;
+                        }
+                    }
+                    self.decoder.feed(&bytes);
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    let err = OpencodeError::Connection {
+                        message: e.to_string(),
+                        source: Some(Box::new(e)),
+                    };
+                    if let Some(err) = self.connection_lost(Some(err)) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                Poll::Ready(None) => {
+                    // Stream ended.  Without reconnection, flush any partial
+                    // event; otherwise it is dropped, as the spec requires,
+                    // and resumed from the last complete event's ID.
+                    if self.reconnect.is_none() {
+                        self.decoder.finish();
+                    }
+                    if let Some(err) = self.connection_lost(None) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                Poll::Pending => {
+                    if self.poll_idle(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    tracing::debug!("event stream idle timeout");
+                    if let Some(err) = self.connection_lost(Some(OpencodeError::Timeout)) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+            }
+        }
     }
 }
 
+/// Decode an event's `data` as JSON, skipping events without data
+/// (heartbeats, etc.).
+fn decode_data<T: DeserializeOwned>(event: &ServerSentEvent) -> Option<Result<T, OpencodeError>> {
+    (!event.data.is_empty())
+        .then(|| serde_json::from_str::<T>(&event.data).map_err(OpencodeError::Serialization))
+}
+
 // ---------------------------------------------------------------------------
-// SseStream
+// RawSseStream
 // ---------------------------------------------------------------------------
 
-pin_project! {
-    /// A stream of typed items parsed from Server-Sent Events.
+/// A stream of undecoded [`ServerSentEvent`]s.
+///
+/// Yields every event as received — including its `event` type and `id`,
+/// and events without data — so callers can log, persist or proxy them
+/// without a JSON round-trip.  The stream ends on the first transport error
+/// or when the server closes it.
+pub struct RawSseStream {
+    source: SseSource,
+}
+
+impl RawSseStream {
+    /// Create a `RawSseStream` from an hpx response byte stream.
+    pub(crate) fn new(byte_stream: ByteStream) -> Self {
+        Self { source: SseSource::new(byte_stream, None) }
+    }
+
+    /// The ID of the last event received, if any.
+    #[must_use]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.source.last_event_id.as_deref()
+    }
+
+    /// Fail the stream with [`OpencodeError::Timeout`] if nothing — not even
+    /// a comment or heartbeat — arrives for `timeout`.
     ///
-    /// Wraps an inner byte stream (from `hpx::Response::bytes_stream()`)
-    /// and parses each SSE event's `data` field as JSON of type `T`.
-    pub struct SseStream<T> {
-        #[pin]
-        inner: Pin<Box<dyn Stream<Item = Result<Bytes, hpx::Error>> + Send>>,
-        decoder: SseDecoder,
-        pending: Vec<ServerSentEvent>,
-        _marker: std::marker::PhantomData<T>,
+    /// Event streams are sent without the client's request timeout, so this
+    /// is what detects a half-open connection.
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.source.set_idle_timeout(timeout);
+        self
+    }
+
+    /// When bytes last arrived on the connection, or when it was opened if
+    /// none have.
+    #[must_use]
+    pub fn last_activity(&self) -> std::time::Instant {
+        self.source.last_activity.into_std()
     }
 }
 
+impl Stream for RawSseStream {
+    type Item = Result<ServerSentEvent, OpencodeError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match std::task::ready!(this.source.poll_next(cx)) {
+                Some(Ok(SourceItem::Event(event))) => return Poll::Ready(Some(Ok(event))),
+                Some(Ok(SourceItem::Reconnected)) => {}
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SseStream
+// ---------------------------------------------------------------------------
+
+/// A stream of typed items parsed from Server-Sent Events.
+///
+/// Layered on a [`RawSseStream`], it parses each event's `data` field as
+/// JSON of type `T`, skipping events without data.  The stream ends on the
+/// first transport error or when the server closes it; see
+/// [`ReconnectingSseStream`] for a stream that survives both.
+pub struct SseStream<T> {
+    raw: RawSseStream,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
 impl<T: DeserializeOwned> SseStream<T> {
     /// Create an `SseStream` from an hpx response byte stream.
-    pub(crate) fn new(
-        byte_stream: impl Stream<Item = Result<Bytes, hpx::Error>> + Send + 'static,
-    ) -> Self {
-        Self {
-            inner: Box::pin(byte_stream),
-            decoder: SseDecoder::new(),
-            pending: Vec::new(),
-            _marker: std::marker::PhantomData,
-        }
+    pub(crate) fn new(byte_stream: ByteStream) -> Self {
+        Self::from(RawSseStream::new(byte_stream))
+    }
+
+    /// The ID of the last event received, if any.
+    #[must_use]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.raw.last_event_id()
+    }
+
+    /// Fail the stream with [`OpencodeError::Timeout`] if nothing arrives for
+    /// `timeout`; see [`RawSseStream::idle_timeout`].
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.raw = self.raw.idle_timeout(timeout);
+        self
+    }
+
+    /// When bytes last arrived on the connection, or when it was opened if
+    /// none have.
+    #[must_use]
+    pub fn last_activity(&self) -> std::time::Instant {
+        self.raw.last_activity()
+    }
+}
+
+impl<T: DeserializeOwned> From<RawSseStream> for SseStream<T> {
+    fn from(raw: RawSseStream) -> Self {
+        Self { raw, _marker: std::marker::PhantomData }
     }
 }
 
@@ -194,45 +632,113 @@ impl<T: DeserializeOwned> Stream for SseStream<T> {
     type Item = Result<T, OpencodeError>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-
-        // First, drain any pending events from a previous chunk.
-        if !this.pending.is_empty() {
-            let event = this.pending.remove(0);
-            if event.data.is_empty() {
-                // Skip events with no data (heartbeats, etc.).
-                cx.waker().wake_by_ref();
-                return Poll::Pending;
+        let this = self.get_mut();
+        loop {
+            match std::task::ready!(Pin::new(&mut this.raw).poll_next(cx)) {
+                Some(Ok(event)) => {
+                    if let Some(parsed) = decode_data(&event) {
+                        return Poll::Ready(Some(parsed));
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
             }
-            let parsed =
-                serde_json::from_str::<T>(&event.data).map_err(OpencodeError::Serialization);
-            return Poll::Ready(Some(parsed));
         }
+    }
+}
 
-        // Poll the inner byte stream for more data.
-        match this.inner.as_mut().poll_next(cx) {
-            Poll::Ready(Some(Ok(bytes))) => {
-                let events = this.decoder.feed(&bytes);
-                *this.pending = events;
-                cx.waker().wake_by_ref();
-                Poll::Pending
-            }
-            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(OpencodeError::Connection {
-                message: e.to_string(),
-                source: Some(Box::new(e)),
-            }))),
-            Poll::Ready(None) => {
-                // Stream ended — flush any remaining partial event.
-                if let Some(event) = this.decoder.flush() &&
-                    !event.data.is_empty()
-                {
-                    let parsed = serde_json::from_str::<T>(&event.data)
-                        .map_err(OpencodeError::Serialization);
-                    return Poll::Ready(Some(parsed));
+// ---------------------------------------------------------------------------
+// ReconnectingSseStream
+// ---------------------------------------------------------------------------
+
+/// An item from a [`ReconnectingSseStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SseItem<T> {
+    /// A decoded event.
+    Event(T),
+    /// The connection was lost and has been re-established.
+    ///
+    /// Events sent while disconnected may have been missed if the server does
+    /// not replay from `Last-Event-ID`, so consumers should resync any state
+    /// they derive from the stream.
+    Reconnected,
+}
+
+/// A typed SSE stream that reconnects after transport errors and EOF.
+///
+/// Reconnection backs off exponentially (see [`ReconnectOptions`]), honours
+/// the server's `retry:` field and sends `Last-Event-ID` so the server can
+/// resume.  Each successful reconnection is reported as
+/// [`SseItem::Reconnected`].  An event cut off by the disconnect is
+/// discarded; the server resends it after the last complete event's ID.  The
+/// stream only ends with an error once reconnection is given up: after a
+/// non-retryable error response or when [`ReconnectOptions::max_attempts`]
+/// is exhausted.
+pub struct ReconnectingSseStream<T> {
+    source: SseSource,
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> ReconnectingSseStream<T> {
+    /// Wrap an already open connection; `connector` re-opens it.  Once
+    /// `cancel` fires, a pending reconnection fails with
+    /// [`OpencodeError::UserAbort`] and the stream ends.
+    pub(crate) fn new(
+        stream: ByteStream,
+        connector: Connector,
+        options: ReconnectOptions,
+        cancel: Option<CancellationToken>,
+    ) -> Self {
+        let reconnect = Reconnect { connector, options, failures: 0, cancel, attempt: None };
+        Self { source: SseSource::new(stream, Some(reconnect)), _marker: std::marker::PhantomData }
+    }
+
+    /// The ID of the last event received, sent as `Last-Event-ID` when
+    /// reconnecting.
+    #[must_use]
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.source.last_event_id.as_deref()
+    }
+
+    /// Treat the connection as lost and reconnect if nothing — not even a
+    /// comment or heartbeat — arrives for `timeout`.  Each reconnection
+    /// attempt must also get a response within `timeout`, or it counts as
+    /// failed.
+    ///
+    /// Event streams are sent without the client's request timeout, so this
+    /// is what detects a half-open connection.
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.source.set_idle_timeout(timeout);
+        self
+    }
+
+    /// When bytes last arrived on the connection, or when it was last
+    /// (re-)established if none have since.
+    #[must_use]
+    pub fn last_activity(&self) -> std::time::Instant {
+        self.source.last_activity.into_std()
+    }
+}
+
+impl<T: DeserializeOwned> Stream for ReconnectingSseStream<T> {
+    type Item = Result<SseItem<T>, OpencodeError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match std::task::ready!(this.source.poll_next(cx)) {
+                Some(Ok(SourceItem::Event(event))) => {
+                    if let Some(parsed) = decode_data(&event) {
+                        return Poll::Ready(Some(parsed.map(SseItem::Event)));
+                    }
+                }
+                Some(Ok(SourceItem::Reconnected)) => {
+                    return Poll::Ready(Some(Ok(SseItem::Reconnected)));
                 }
-                Poll::Ready(None)
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
             }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
@@ -245,10 +751,16 @@ impl<T: DeserializeOwned> Stream for SseStream<T> {
 mod tests {
     use super::*;
 
+    /// Feed `chunk` and collect every event it completes.
+    fn decode(decoder: &mut SseDecoder, chunk: &[u8]) -> Vec<ServerSentEvent> {
+        decoder.feed(chunk);
+        std::iter::from_fn(|| decoder.next_event()).collect()
+    }
+
     #[test]
     fn test_parse_simple_event() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data: {\"key\":\"value\"}\n\n");
+        let events = decode(&mut decoder, b"data: {\"key\":\"value\"}\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].data, "{\"key\":\"value\"}");
         assert!(events[0].event.is_none());
@@ -257,7 +769,7 @@ mod tests {
     #[test]
     fn test_parse_event_with_type() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"event: message\ndata: hello\n\n");
+        let events = decode(&mut decoder, b"event: message\ndata: hello\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].event.as_deref(), Some("message"));
         assert_eq!(events[0].data, "hello");
@@ -266,7 +778,7 @@ mod tests {
     #[test]
     fn test_parse_multiline_data() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data: line1\ndata: line2\n\n");
+        let events = decode(&mut decoder, b"data: line1\ndata: line2\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].data, "line1\nline2");
     }
@@ -274,7 +786,7 @@ mod tests {
     #[test]
     fn test_parse_multiple_events() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data: event1\n\ndata: event2\n\n");
+        let events = decode(&mut decoder, b"data: event1\n\ndata: event2\n\n");
         assert_eq!(events.len(), 2);
         assert_eq!(events[0].data, "event1");
         assert_eq!(events[1].data, "event2");
@@ -283,7 +795,7 @@ mod tests {
     #[test]
     fn test_ignore_comments() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b": this is a comment\ndata: actual\n\n");
+        let events = decode(&mut decoder, b": this is a comment\ndata: actual\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].data, "actual");
     }
@@ -291,9 +803,9 @@ mod tests {
     #[test]
     fn test_chunked_data() {
         let mut decoder = SseDecoder::new();
-        let events1 = decoder.feed(b"data: hel");
+        let events1 = decode(&mut decoder, b"data: hel");
         assert!(events1.is_empty());
-        let events2 = decoder.feed(b"lo\n\n");
+        let events2 = decode(&mut decoder, b"lo\n\n");
         assert_eq!(events2.len(), 1);
         assert_eq!(events2[0].data, "hello");
     }
@@ -301,7 +813,7 @@ mod tests {
     #[test]
     fn test_id_field() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"id: 42\ndata: test\n\n");
+        let events = decode(&mut decoder, b"id: 42\ndata: test\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].id.as_deref(), Some("42"));
         assert_eq!(events[0].data, "test");
@@ -310,9 +822,10 @@ mod tests {
     #[test]
     fn test_flush_remaining() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data: partial");
+        let events = decode(&mut decoder, b"data: partial");
         assert!(events.is_empty());
-        let event = decoder.flush();
+        decoder.finish();
+        let event = decoder.next_event();
         assert!(event.is_some());
         assert_eq!(event.as_ref().unwrap().data, "partial");
     }
@@ -321,14 +834,14 @@ mod tests {
     fn test_empty_line_no_data() {
         let mut decoder = SseDecoder::new();
         // An empty line without prior fields produces nothing.
-        let events = decoder.feed(b"\n");
+        let events = decode(&mut decoder, b"\n");
         assert!(events.is_empty());
     }
 
     #[test]
     fn test_field_without_value() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data\n\n");
+        let events = decode(&mut decoder, b"data\n\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].data, "");
     }
@@ -336,11 +849,69 @@ mod tests {
     #[test]
     fn test_crlf_line_endings() {
         let mut decoder = SseDecoder::new();
-        let events = decoder.feed(b"data: hello\r\n\r\n");
+        let events = decode(&mut decoder, b"data: hello\r\n\r\n");
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].data, "hello");
     }
 
+    #[test]
+    fn test_retry_field() {
+        let mut decoder = SseDecoder::new();
+        decode(&mut decoder, b"retry: 2500\n\nretry: soon\n\n");
+        assert_eq!(decoder.retry(), Some(Duration::from_millis(2500)));
+        decode(&mut decoder, b"data: partial");
+        decoder.reset();
+        assert_eq!(decoder.retry(), Some(Duration::from_millis(2500)));
+        decoder.finish();
+        assert!(decoder.next_event().is_none());
+    }
+
+    #[test]
+    fn test_cr_only_line_endings() {
+        let mut decoder = SseDecoder::new();
+        let events = decode(&mut decoder, b"event: a\rdata: one\r\rdata: two\r\r");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event.as_deref(), Some("a"));
+        assert_eq!(events[0].data, "one");
+        assert_eq!(events[1].data, "two");
+    }
+
+    #[test]
+    fn test_crlf_split_across_chunks() {
+        let mut decoder = SseDecoder::new();
+        assert!(decode(&mut decoder, b"data: one\r").is_empty());
+        // The `\n` completes the previous line ending; it is not an empty line.
+        assert!(decode(&mut decoder, b"\ndata: two\r").is_empty());
+        let events = decode(&mut decoder, b"\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "one\ntwo");
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        let payload = "data: {\"text\":\"héllo wörld ✓ 🚀\"}\n\n".as_bytes();
+        for split in 0..payload.len() {
+            let mut decoder = SseDecoder::new();
+            let mut events = decode(&mut decoder, &payload[..split]);
+            events.extend(decode(&mut decoder, &payload[split..]));
+            assert_eq!(events.len(), 1, "split at {split}");
+            assert_eq!(events[0].data, "{\"text\":\"héllo wörld ✓ 🚀\"}", "split at {split}");
+        }
+    }
+
+    #[test]
+    fn test_byte_by_byte_matches_whole() {
+        let payload = b"id: 1\r\nevent: x\r\ndata: a\r\ndata: b\r\n\r\n: ping\n\ndata: c\r\r";
+        let mut whole = SseDecoder::new();
+        let expected = decode(&mut whole, payload);
+        assert_eq!(expected.len(), 2);
+
+        let mut decoder = SseDecoder::new();
+        let events: Vec<_> =
+            payload.iter().flat_map(|b| decode(&mut decoder, std::slice::from_ref(b))).collect();
+        assert_eq!(events, expected);
+    }
+
     #[test]
     fn test_sse_stream_typed_compiles() {
         // Verify that SseStream implements Stream with the expected Item.
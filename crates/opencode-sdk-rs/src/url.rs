@@ -0,0 +1,229 @@
+//! Percent-encoding helpers used to build request URLs.
+//!
+//! Path segments go through [`PathSegment`] (usually via the crate-internal
+//! `encode_path!` macro) and query strings through [`QueryPairs`].  Both encode every
+//! byte outside the RFC 3986 unreserved set, so IDs and values containing
+//! `&`, `#`, `/`, spaces or non-ASCII characters reach the server intact.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::OpencodeError;
+
+/// Append `input` to `out`, percent-encoding everything except the RFC 3986
+/// unreserved characters (`A-Z a-z 0-9 - . _ ~`).
+fn percent_encode_into(out: &mut impl fmt::Write, input: &str) -> fmt::Result {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    for &byte in input.as_bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.write_char(char::from(byte))?;
+        } else {
+            out.write_char('%')?;
+            out.write_char(char::from(HEX[usize::from(byte >> 4)]))?;
+            out.write_char(char::from(HEX[usize::from(byte & 0x0F)]))?;
+        }
+    }
+    Ok(())
+}
+
+/// A single path segment that is percent-encoded when displayed.
+///
+/// `/` is encoded too, so a value can never escape its segment.
+#[derive(Debug, Clone, Copy)]
+pub struct PathSegment<'a>(pub &'a str);
+
+impl fmt::Display for PathSegment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        percent_encode_into(f, self.0)
+    }
+}
+
+/// Format a request path, percent-encoding every interpolated argument as a
+/// [`PathSegment`]:
+///
+/// ```ignore
+/// encode_path!("/session/{}/message", id)
+/// ```
+macro_rules! encode_path {
+    ($fmt:literal $(, $segment:expr)* $(,)?) => {
+        format!($fmt $(, $crate::url::PathSegment($segment))*)
+    };
+}
+pub(crate) use encode_path;
+
+/// An ordered list of query parameters in which keys may repeat.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryPairs(Vec<(String, String)>);
+
+impl QueryPairs {
+    /// Flatten a serialisable params struct into query pairs.
+    ///
+    /// `None`/`null` fields are skipped, scalars are rendered as text and
+    /// sequences become one pair per element.  Nested objects have no
+    /// query-string representation and are rejected.
+    pub fn from_serialize<Q: Serialize + ?Sized>(query: &Q) -> Result<Self, OpencodeError> {
+        let fields = match serde_json::to_value(query)? {
+            serde_json::Value::Null => return Ok(Self::default()),
+            serde_json::Value::Object(fields) => fields,
+            _ => return Err(unsupported("query parameters must serialise to a map")),
+        };
+
+        let mut pairs = Vec::with_capacity(fields.len());
+        for (key, value) in fields {
+            match value {
+                serde_json::Value::Array(items) => {
+                    for item in items {
+                        if let Some(text) = scalar_to_string(item, &key)? {
+                            pairs.push((key.clone(), text));
+                        }
+                    }
+                }
+                other => {
+                    if let Some(text) = scalar_to_string(other, &key)? {
+                        pairs.push((key, text));
+                    }
+                }
+            }
+        }
+        Ok(Self(pairs))
+    }
+
+    /// Apply a higher-precedence layer: every key that appears in `layer`
+    /// drops all of its existing values, then the layer's pairs are appended
+    /// in order.
+    pub fn override_with<'a>(&mut self, layer: impl IntoIterator<Item = &'a (String, String)>) {
+        let layer: Vec<_> = layer.into_iter().collect();
+        self.0.retain(|(key, _)| !layer.iter().any(|(k, _)| k == key));
+        self.0.extend(layer.into_iter().cloned());
+    }
+
+    /// Whether there are no pairs.
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Render as an `application/x-www-form-urlencoded`-compatible query
+    /// string (without the leading `?`).  Spaces are encoded as `%20`.
+    pub fn encode(&self) -> String {
+        let mut out = String::new();
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                out.push('&');
+            }
+            // Writing to a `String` cannot fail.
+            let _ = percent_encode_into(&mut out, key);
+            out.push('=');
+            let _ = percent_encode_into(&mut out, value);
+        }
+        out
+    }
+}
+
+impl FromIterator<(String, String)> for QueryPairs {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a QueryPairs {
+    type Item = &'a (String, String);
+    type IntoIter = std::slice::Iter<'a, (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Render a scalar JSON value as query text; `null` yields `None`.
+fn scalar_to_string(value: serde_json::Value, key: &str) -> Result<Option<String>, OpencodeError> {
+    match value {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::String(s) => Ok(Some(s)),
+        serde_json::Value::Bool(b) => Ok(Some(b.to_string())),
+        serde_json::Value::Number(n) => Ok(Some(n.to_string())),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => Err(unsupported(format!(
+            "query parameter `{key}` must be a scalar or a list of scalars"
+        ))),
+    }
+}
+
+fn unsupported(message: impl fmt::Display) -> OpencodeError {
+    OpencodeError::Serialization(<serde_json::Error as serde::ser::Error>::custom(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> QueryPairs {
+        items.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())).collect()
+    }
+
+    #[test]
+    fn path_segment_encodes_reserved_characters() {
+        assert_eq!(PathSegment("ses_01ABC-x.y~z").to_string(), "ses_01ABC-x.y~z");
+        assert_eq!(PathSegment("a b/c?d#e&f").to_string(), "a%20b%2Fc%3Fd%23e%26f");
+        assert_eq!(PathSegment("café").to_string(), "caf%C3%A9");
+    }
+
+    #[test]
+    fn path_macro_encodes_each_argument() {
+        let id = "../admin";
+        let message = "msg 1";
+        assert_eq!(
+            super::encode_path!("/session/{}/message/{}", id, message),
+            "/session/..%2Fadmin/message/msg%201"
+        );
+    }
+
+    #[test]
+    fn encode_escapes_keys_and_values() {
+        let q = pairs(&[("path", "src/a b.rs"), ("q", "x&y=z#frag"), ("k=1", "+")]);
+        assert_eq!(q.encode(), "path=src%2Fa%20b.rs&q=x%26y%3Dz%23frag&k%3D1=%2B");
+    }
+
+    #[test]
+    fn from_serialize_flattens_scalars_and_sequences() {
+        let q = QueryPairs::from_serialize(&json!({
+            "query": "foo",
+            "limit": 10,
+            "dirs": true,
+            "skip": null,
+            "type": ["file", "directory"]
+        }))
+        .unwrap();
+        assert_eq!(
+            q,
+            pairs(&[
+                ("dirs", "true"),
+                ("limit", "10"),
+                ("query", "foo"),
+                ("type", "file"),
+                ("type", "directory"),
+            ])
+        );
+    }
+
+    #[test]
+    fn from_serialize_unit_is_empty() {
+        assert!(QueryPairs::from_serialize(&()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn from_serialize_rejects_nested_objects() {
+        let err = QueryPairs::from_serialize(&json!({ "filter": { "a": 1 } })).unwrap_err();
+        assert!(matches!(err, OpencodeError::Serialization(_)));
+        assert!(QueryPairs::from_serialize(&json!("bare")).is_err());
+    }
+
+    #[test]
+    fn override_with_replaces_every_value_of_a_key() {
+        let mut q = pairs(&[("a", "1"), ("tag", "x"), ("tag", "y"), ("b", "2")]);
+        q.override_with(&[("tag".to_owned(), "z".to_owned()), ("c".to_owned(), "3".to_owned())]);
+        assert_eq!(q, pairs(&[("a", "1"), ("b", "2"), ("tag", "z"), ("c", "3")]));
+    }
+}
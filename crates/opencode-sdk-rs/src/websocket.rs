@@ -0,0 +1,203 @@
+//! WebSocket client support.
+//!
+//! The handshake (including `Sec-WebSocket-Accept` validation) and the frame
+//! codec come from `hpx`'s WebSocket support.  On top of that, a connection
+//! is driven by two background tasks so both ends can be used from
+//! `tokio::select!`:
+//!
+//! - the reader task owns the read half, forwards text and binary payloads over a channel and
+//!   queues pongs and the close echo for the writer;
+//! - the writer task owns the write half and sends whatever is queued, in order.
+//!
+//! [`WebSocketReader::recv`] is therefore just a channel receive and is
+//! cancel-safe; control frames are answered even while nobody is reading.
+
+use bytes::Bytes;
+use hpx::ws::{
+    WebSocketRead, WebSocketWrite,
+    message::{CloseCode, CloseFrame, Message, Utf8Bytes},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+
+use crate::error::OpencodeError;
+
+/// Payloads buffered between the reader task and [`WebSocketReader::recv`].
+const READ_BUFFER: usize = 64;
+
+/// Messages buffered for the writer task.
+const WRITE_BUFFER: usize = 16;
+
+/// RFC 6455 limit on control frame payloads.
+const MAX_CONTROL_PAYLOAD: usize = 125;
+
+/// Close status code for a normal closure.
+const CLOSE_NORMAL: u16 = 1000;
+
+/// A message queued for the writer task.
+struct Outgoing {
+    message: Message,
+    /// Completed with the outcome of the write; `None` for control replies.
+    ack: Option<oneshot::Sender<Result<(), OpencodeError>>>,
+}
+
+/// Split an established WebSocket into a reader and a writer, each backed by
+/// a background task.
+pub fn split(socket: hpx::ws::WebSocket) -> (WebSocketReader, WebSocketWriter) {
+    let (write, read) = socket.split();
+    let (queue, queued) = mpsc::channel(WRITE_BUFFER);
+    let (output, incoming) = mpsc::channel(READ_BUFFER);
+
+    tokio::spawn(write_loop(write, queued));
+    let task = tokio::spawn(read_loop(read, output, queue.clone()));
+
+    (WebSocketReader { incoming, task }, WebSocketWriter { queue })
+}
+
+/// Read frames until the connection ends, forwarding data and queuing
+/// control replies.
+async fn read_loop(
+    mut read: WebSocketRead,
+    output: mpsc::Sender<Result<Bytes, OpencodeError>>,
+    queue: mpsc::Sender<Outgoing>,
+) {
+    loop {
+        let message = match read.recv().await {
+            Some(Ok(message)) => message,
+            Some(Err(err)) => {
+                let _ = output.send(Err(transport_error(err))).await;
+                return;
+            }
+            None => return,
+        };
+
+        let data = match message {
+            Message::Text(text) => Bytes::from(text),
+            Message::Binary(data) => data,
+            Message::Ping(payload) => {
+                // `hpx` already rejects oversized pings.
+                let pong = Outgoing { message: Message::Pong(payload), ack: None };
+                if queue.send(pong).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+            Message::Pong(payload) if payload.len() > MAX_CONTROL_PAYLOAD => {
+                let _ = output.send(Err(protocol_error("oversized pong frame"))).await;
+                return;
+            }
+            Message::Pong(_) => continue,
+            Message::Close(frame) => {
+                if frame.as_ref().is_some_and(|f| f.reason.len() + 2 > MAX_CONTROL_PAYLOAD) {
+                    let _ = output.send(Err(protocol_error("oversized close frame"))).await;
+                    return;
+                }
+                // Echo the status code back; the writer drops it if we
+                // already sent a close frame.
+                let echo = frame.map(|f| CloseFrame { code: f.code, reason: Utf8Bytes::from("") });
+                let _ = queue.send(Outgoing { message: Message::Close(echo), ack: None }).await;
+                return;
+            }
+        };
+
+        if output.send(Ok(data)).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Send queued messages in order until a close frame has been written or
+/// every sender is gone.
+async fn write_loop(mut write: WebSocketWrite, mut queued: mpsc::Receiver<Outgoing>) {
+    while let Some(Outgoing { message, ack }) = queued.recv().await {
+        let closing = matches!(message, Message::Close(_));
+        let result = write.send(message).await.map_err(transport_error);
+        let failed = result.is_err();
+        if let Some(ack) = ack {
+            let _ = ack.send(result);
+        }
+        if closing || failed {
+            return;
+        }
+    }
+}
+
+fn transport_error(err: hpx::Error) -> OpencodeError {
+    OpencodeError::Connection { message: err.to_string(), source: Some(Box::new(err)) }
+}
+
+fn protocol_error(message: &str) -> OpencodeError {
+    OpencodeError::Connection {
+        message: format!("WebSocket protocol error: {message}"),
+        source: None,
+    }
+}
+
+fn closed_error() -> OpencodeError {
+    OpencodeError::Connection { message: "WebSocket connection is closed".to_owned(), source: None }
+}
+
+// ---------------------------------------------------------------------------
+// Halves
+// ---------------------------------------------------------------------------
+
+/// The receiving half of a WebSocket connection.
+///
+/// Dropping it stops reading from the connection.
+#[derive(Debug)]
+pub struct WebSocketReader {
+    incoming: mpsc::Receiver<Result<Bytes, OpencodeError>>,
+    task: JoinHandle<()>,
+}
+
+impl WebSocketReader {
+    /// Receive the payload of the next text or binary message.
+    ///
+    /// Returns `None` once the connection is closed.  Cancel-safe: a
+    /// message is never lost if the returned future is dropped.
+    pub async fn recv(&mut self) -> Option<Result<Bytes, OpencodeError>> {
+        self.incoming.recv().await
+    }
+}
+
+impl Drop for WebSocketReader {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The sending half of a WebSocket connection.
+#[derive(Debug, Clone)]
+pub struct WebSocketWriter {
+    queue: mpsc::Sender<Outgoing>,
+}
+
+impl WebSocketWriter {
+    /// Send a text message.
+    pub async fn send_text(&self, text: &str) -> Result<(), OpencodeError> {
+        self.send(Message::text(text)).await
+    }
+
+    /// Send a binary message.
+    pub async fn send_binary(&self, data: &[u8]) -> Result<(), OpencodeError> {
+        self.send(Message::binary(data.to_vec())).await
+    }
+
+    /// Send a normal-closure close frame.
+    pub async fn close(&self) -> Result<(), OpencodeError> {
+        let frame = CloseFrame { code: CloseCode::from(CLOSE_NORMAL), reason: Utf8Bytes::from("") };
+        self.send(Message::Close(Some(frame))).await
+    }
+
+    /// Queue `message` and wait until it has been written.
+    ///
+    /// If the returned future is dropped after the message was queued, the
+    /// message is still sent.
+    async fn send(&self, message: Message) -> Result<(), OpencodeError> {
+        let (ack, written) = oneshot::channel();
+        self.queue.send(Outgoing { message, ack: Some(ack) }).await.map_err(|_| closed_error())?;
+        written.await.unwrap_or_else(|_| Err(closed_error()))
+    }
+}
@@ -1,11 +1,41 @@
+use std::{
+    future::poll_fn,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures_core::Stream;
 use opencode_sdk_rs::{
-    Opencode,
+    CancellationToken, EventRouter, Opencode, OpencodeError, RequestOptions,
     config::ClientOptions,
-    resources::file::{FileListParams, FileReadParams},
+    middleware::{BoxFuture, Middleware, MiddlewareRequest, MiddlewareResponse},
+    resources::{
+        agent::AgentMode,
+        auth::AuthInfo,
+        config::ConfigPatch,
+        event::{EventListResponse, PermissionReply, ToastVariant},
+        file::{FileListParams, FileReadParams},
+        lsp::LspServerStatus,
+        mcp::{McpAuthCallbackParams, McpStatus},
+        permission::PermissionReplyParams,
+        provider::{
+            ProviderAuthAuthorizationMethod, ProviderOauthAuthorizeParams,
+            ProviderOauthCallbackParams,
+        },
+        pty::{PtyCreateParams, PtySize, PtyUpdateParams},
+        question::QuestionReplyParams,
+        session::{
+            Message, SessionChatParams, SessionCommandParams, SessionDiffParams, SessionForkParams,
+            SessionShellParams, SessionStatus, SessionUpdateParams,
+        },
+        tui::{TuiSelectSessionParams, TuiShowToastParams},
+        worktree::{WorktreeCreateParams, WorktreeDirectoryParams, WorktreeSetup},
+    },
+    streaming::{ReconnectOptions, ServerSentEvent, SseItem, SseStream},
 };
 use wiremock::{
     Mock, MockServer, ResponseTemplate,
-    matchers::{method, path, query_param},
+    matchers::{body_json, header, method, path, query_param},
 };
 
 /// Helper: create a client pointing at the mock server with no retries.
@@ -91,6 +121,70 @@ async fn test_app_providers() {
     assert_eq!(prov.providers[0].id, "anthropic");
 }
 
+// ---------------------------------------------------------------------------
+// Agent, Command & Skill
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_agent_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/agent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "name": "build", "mode": "primary", "native": true, "permission": [], "options": {} },
+            { "name": "general", "mode": "subagent", "native": true, "permission": [], "options": {} }
+        ])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let agents = client.agent().list(None).await.unwrap();
+    assert_eq!(agents.len(), 2);
+    assert!(agents.iter().any(|a| a.name == "general" && a.mode == AgentMode::Subagent));
+}
+
+#[tokio::test]
+async fn test_command_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/command"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "name": "review",
+            "description": "Review the current diff",
+            "agent": "plan",
+            "template": "Review $ARGUMENTS",
+            "subtask": true,
+            "hints": ["$ARGUMENTS"]
+        }])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let commands = client.command().list(None).await.unwrap();
+    assert_eq!(commands[0].name, "review");
+    assert_eq!(commands[0].agent.as_deref(), Some("plan"));
+    assert_eq!(commands[0].subtask, Some(true));
+}
+
+#[tokio::test]
+async fn test_skill_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/skill"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "name": "release-notes",
+            "description": "Write release notes",
+            "location": "/repo/.opencode/skill/release-notes/SKILL.md",
+            "content": "# Release notes"
+        }])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let skills = client.skill().list(None).await.unwrap();
+    assert_eq!(skills[0].location, "/repo/.opencode/skill/release-notes/SKILL.md");
+}
+
 // ---------------------------------------------------------------------------
 // Session
 // ---------------------------------------------------------------------------
@@ -135,6 +229,108 @@ async fn test_session_list() {
     assert_eq!(sessions.len(), 2);
 }
 
+#[tokio::test]
+async fn test_scoped_client_targets_directory() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/session"))
+        .and(query_param("directory", "/work/other"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/session"))
+        .and(query_param("directory", "/work/repo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "id": "s1", "slug": "s1", "projectID": "p1", "directory": "/work/repo", "time": { "created": 1.0, "updated": 2.0 }, "title": "S1", "version": "1" }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(query_param("directory", "/work/repo"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(
+                    "data: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+                ),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server).scoped("/work/repo");
+    let sessions = client.session().list(None).await.unwrap();
+    assert_eq!(sessions[0].directory, "/work/repo");
+
+    let opts = RequestOptions { directory: Some("/work/other".to_owned()), ..Default::default() };
+    assert!(client.session().list(Some(&opts)).await.unwrap().is_empty());
+
+    let mut events = client.event().list(None).await.unwrap();
+    let event = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(event, EventListResponse::VcsBranchUpdated { .. }));
+}
+
+#[tokio::test]
+async fn test_event_stream_request_options_survive_reconnect() {
+    let server = MockServer::start().await;
+    let event = |id: u32, branch: &str| {
+        format!(
+            "id: {id}\ndata: {{\"type\":\"vcs.branch.updated\",\"properties\":{{\"branch\":\"{branch}\"}}}}\n\n"
+        )
+    };
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(query_param("directory", "/work/other"))
+        .and(query_param("verbose", "1"))
+        .and(header("x-trace", "abc"))
+        .and(header("last-event-id", "1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(event(2, "dev")),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(query_param("directory", "/work/other"))
+        .and(query_param("verbose", "1"))
+        .and(header("x-trace", "abc"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(event(1, "main")),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+
+    let mut extra_headers = http::HeaderMap::new();
+    extra_headers.insert("x-trace", http::HeaderValue::from_static("abc"));
+    let opts = RequestOptions {
+        directory: Some("/work/other".to_owned()),
+        query: Some(vec![("verbose".to_owned(), "1".to_owned())]),
+        extra_headers: Some(extra_headers),
+        ..Default::default()
+    };
+    let client = client_for(&server).scoped("/work/repo");
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client.event().list_reconnecting(reconnect, Some(&opts)).await.unwrap();
+
+    let mut branches = Vec::new();
+    while branches.len() < 2 {
+        let item = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+        if let SseItem::Event(EventListResponse::VcsBranchUpdated { properties }) = item {
+            branches.push(properties.branch.unwrap_or_default());
+        }
+    }
+    assert_eq!(branches, ["main", "dev"]);
+}
+
 #[tokio::test]
 async fn test_session_delete() {
     let server = MockServer::start().await;
@@ -149,6 +345,171 @@ async fn test_session_delete() {
     assert!(result);
 }
 
+#[tokio::test]
+async fn test_session_get_update_and_fork() {
+    let server = MockServer::start().await;
+    let session = |id: &str, title: &str| {
+        serde_json::json!({
+            "id": id,
+            "time": { "created": 100.0, "updated": 200.0 },
+            "title": title,
+            "version": "1.0"
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/session/ses_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(session("ses_1", "Original")))
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/session/ses_1"))
+        .and(body_json(serde_json::json!({ "title": "Renamed" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(session("ses_1", "Renamed")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/session/ses_1/fork"))
+        .and(body_json(serde_json::json!({ "messageID": "msg_3" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(session("ses_2", "Original (fork)")))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let fetched = client.session().get("ses_1", None).await.unwrap();
+    assert_eq!(fetched.title, "Original");
+
+    let params = SessionUpdateParams { title: Some("Renamed".into()), ..Default::default() };
+    let updated = client.session().update("ses_1", &params, None).await.unwrap();
+    assert_eq!(updated.title, "Renamed");
+
+    let params = SessionForkParams { message_id: Some("msg_3".into()) };
+    let fork = client.session().fork("ses_1", &params, None).await.unwrap();
+    assert_eq!(fork.id, "ses_2");
+}
+
+#[tokio::test]
+async fn test_session_children_diff_todo_and_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/session/ses_1/children"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "id": "ses_2",
+            "parentID": "ses_1",
+            "time": { "created": 100.0, "updated": 200.0 },
+            "title": "Subtask",
+            "version": "1.0"
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/session/ses_1/diff"))
+        .and(query_param("messageID", "msg_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "file": "src/lib.rs",
+            "before": "",
+            "after": "pub fn x() {}\n",
+            "additions": 1,
+            "deletions": 0,
+            "status": "added"
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/session/ses_1/todo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "content": "Write tests", "status": "in_progress", "priority": "high" }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/session/status"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "ses_1": { "type": "busy" } })),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let children = client.session().children("ses_1", None).await.unwrap();
+    assert_eq!(children[0].parent_id.as_deref(), Some("ses_1"));
+
+    let params = SessionDiffParams { message_id: Some("msg_1".into()) };
+    let diff = client.session().diff("ses_1", Some(&params), None).await.unwrap();
+    assert_eq!(diff[0].file, "src/lib.rs");
+
+    let todos = client.session().todo("ses_1", None).await.unwrap();
+    assert_eq!(todos[0].status, "in_progress");
+
+    let status = client.session().status(None).await.unwrap();
+    assert_eq!(status["ses_1"], SessionStatus::Busy);
+}
+
+#[tokio::test]
+async fn test_session_prompt_async_accepts_no_content() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/session/ses_1/prompt_async"))
+        .and(body_json(serde_json::json!({ "parts": [{ "type": "text", "text": "Refactor it" }] })))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params: SessionChatParams = serde_json::from_value(serde_json::json!({
+        "parts": [{ "type": "text", "text": "Refactor it" }]
+    }))
+    .unwrap();
+    client.session().prompt_async("ses_1", &params, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_session_command_and_shell() {
+    let server = MockServer::start().await;
+    let assistant = serde_json::json!({
+        "role": "assistant",
+        "id": "msg_2",
+        "sessionID": "ses_1",
+        "parentID": "msg_1",
+        "agent": "build",
+        "time": { "created": 123.0 },
+        "tokens": { "cache": { "read": 0, "write": 0 }, "input": 0, "output": 0, "reasoning": 0 }
+    });
+    Mock::given(method("POST"))
+        .and(path("/session/ses_1/command"))
+        .and(body_json(serde_json::json!({ "command": "review", "arguments": "src/lib.rs" })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "info": assistant, "parts": [] })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/session/ses_1/shell"))
+        .and(body_json(serde_json::json!({ "command": "cargo test", "agent": "build" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(assistant.clone()))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = SessionCommandParams {
+        command: "review".into(),
+        arguments: "src/lib.rs".into(),
+        agent: None,
+        model: None,
+        message_id: None,
+        variant: None,
+        parts: None,
+    };
+    let resp = client.session().command("ses_1", &params, None).await.unwrap();
+    assert!(matches!(resp.info, Message::Assistant(ref msg) if msg.id == "msg_2"));
+
+    let params =
+        SessionShellParams { command: "cargo test".into(), agent: "build".into(), model: None };
+    let msg = client.session().shell("ses_1", &params, None).await.unwrap();
+    assert_eq!(msg.parent_id, "msg_1");
+}
+
 #[tokio::test]
 async fn test_session_chat_accepts_missing_assistant_fields() {
     let server = MockServer::start().await;
@@ -251,189 +612,1822 @@ async fn test_session_chat_accepts_unknown_part_variant() {
 }
 
 // ---------------------------------------------------------------------------
-// File
+// Auth
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_file_read() {
+async fn test_auth_set_and_remove() {
     let server = MockServer::start().await;
-    Mock::given(method("GET"))
-        .and(path("/file/content"))
-        .and(query_param("path", "src/main.rs"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "content": "fn main() {}",
-            "type": "text"
-        })))
+    Mock::given(method("PUT"))
+        .and(path("/auth/anthropic"))
+        .and(body_json(serde_json::json!({ "type": "api", "key": "sk-ant-test" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/auth/anthropic"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let resp =
-        client.file().read(&FileReadParams { path: "src/main.rs".to_owned() }).await.unwrap();
-    assert_eq!(resp.content, "fn main() {}");
+    let auth = AuthInfo::api_key("sk-ant-test");
+    assert!(client.auth().set("anthropic", &auth, None).await.unwrap());
+    assert!(client.auth().remove("anthropic", None).await.unwrap());
 }
 
+// ---------------------------------------------------------------------------
+// Lsp, Formatter & Vcs
+// ---------------------------------------------------------------------------
+
 #[tokio::test]
-async fn test_file_list() {
+async fn test_lsp_and_formatter_status() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/file"))
+        .and(path("/lsp"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
-            { "name": "main.rs", "path": "src/main.rs", "absolute": "/home/user/project/src/main.rs", "type": "file", "ignored": false },
-            { "name": "target", "path": "target", "absolute": "/home/user/project/target", "type": "directory", "ignored": true }
+            { "id": "typescript", "name": "TypeScript", "root": "/repo", "status": "connected" }
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/formatter"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "name": "prettier", "extensions": [".ts", ".tsx"], "enabled": true },
+            { "name": "rustfmt", "extensions": [".rs"], "enabled": false }
         ])))
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let params = FileListParams { path: ".".into() };
-    let files = client.file().list(Some(&params)).await.unwrap();
-    assert_eq!(files.len(), 2);
-    assert_eq!(files[0].name, "main.rs");
-    assert!(!files[0].ignored);
-    assert!(files[1].ignored);
+    let servers = client.lsp().status(None).await.unwrap();
+    assert_eq!(servers[0].status, LspServerStatus::Connected);
+
+    let formatters = client.formatter().status(None).await.unwrap();
+    assert_eq!(formatters.iter().filter(|f| f.enabled).count(), 1);
 }
 
 #[tokio::test]
-async fn test_file_status() {
+async fn test_vcs_get() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/file/status"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
-            { "added": 10, "path": "src/lib.rs", "removed": 2, "status": "modified" }
-        ])))
+        .and(path("/vcs"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "branch": "main" })),
+        )
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let files = client.file().status().await.unwrap();
-    assert_eq!(files.len(), 1);
-    assert_eq!(files[0].path, "src/lib.rs");
+    let vcs = client.vcs().get(None).await.unwrap();
+    assert_eq!(vcs.branch, "main");
 }
 
 // ---------------------------------------------------------------------------
-// Config
+// Mcp
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_config_get() {
+async fn test_mcp_status() {
     let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/config"))
+        .and(path("/mcp"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "theme": "dark",
-            "autoupdate": true
+            "github": { "status": "connected" },
+            "local": { "status": "failed", "error": "spawn ENOENT" }
         })))
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let config = client.config().get(None).await.unwrap();
-    assert_eq!(config.theme.as_deref(), Some("dark"));
-    assert_eq!(config.autoupdate, Some(serde_json::Value::Bool(true)));
+    let status = client.mcp().status(None).await.unwrap();
+    assert_eq!(status["github"], McpStatus::Connected);
+    assert_eq!(status["local"], McpStatus::Failed { error: "spawn ENOENT".into() });
 }
 
-// ---------------------------------------------------------------------------
-// Error handling
-// ---------------------------------------------------------------------------
-
 #[tokio::test]
-async fn test_error_404() {
+async fn test_mcp_connect_disconnect() {
     let server = MockServer::start().await;
-    Mock::given(method("GET"))
-        .and(path("/app"))
-        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+    Mock::given(method("POST"))
+        .and(path("/mcp/github/connect"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mcp/github/disconnect"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let err = client.app().get(None).await.unwrap_err();
-    assert_eq!(err.status(), Some(404));
+    assert!(client.mcp().connect("github", None).await.unwrap());
+    assert!(client.mcp().disconnect("github", None).await.unwrap());
 }
 
 #[tokio::test]
-async fn test_error_500() {
+async fn test_mcp_auth_flow() {
     let server = MockServer::start().await;
-    Mock::given(method("GET"))
-        .and(path("/app"))
-        .respond_with(ResponseTemplate::new(500).set_body_string("server error"))
+    Mock::given(method("POST"))
+        .and(path("/mcp/jira/auth"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "authorizationUrl": "https://auth.example.com/authorize?client_id=abc"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/mcp/jira/auth/callback"))
+        .and(body_json(serde_json::json!({ "code": "xyz" })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "status": "connected" })),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/mcp/jira/auth"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })),
+        )
         .mount(&server)
         .await;
 
     let client = client_for(&server);
-    let err = client.app().get(None).await.unwrap_err();
-    assert_eq!(err.status(), Some(500));
-    assert!(err.is_retryable());
+    let start = client.mcp().auth_start("jira", None).await.unwrap();
+    assert!(start.authorization_url.starts_with("https://auth.example.com/"));
+
+    let params = McpAuthCallbackParams { code: "xyz".into() };
+    let status = client.mcp().auth_callback("jira", &params, None).await.unwrap();
+    assert_eq!(status, McpStatus::Connected);
+
+    assert!(client.mcp().auth_remove("jira", None).await.unwrap().success);
 }
 
 // ---------------------------------------------------------------------------
-// Retry behaviour
+// Permission
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_retry_on_429() {
+async fn test_permission_list() {
     let server = MockServer::start().await;
-
-    // First request returns 429, second returns 200.
+    Mock::given(method("GET"))
+        .and(path("/permission"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "id": "per-1",
+            "sessionID": "sess-1",
+            "permission": "bash",
+            "patterns": ["rm -rf target"],
+            "metadata": {},
+            "always": ["rm *"]
+        }])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let pending = client.permission().list(None).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, "per-1");
+    assert_eq!(pending[0].permission, "bash");
+}
+
+#[tokio::test]
+async fn test_permission_reply() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/permission/per-1/reply"))
+        .and(body_json(serde_json::json!({ "reply": "always" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = PermissionReplyParams { reply: PermissionReply::Always, message: None };
+    let ok = client.permission().reply("per-1", &params, None).await.unwrap();
+    assert!(ok);
+}
+
+// ---------------------------------------------------------------------------
+// Project
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_project_list_and_current() {
+    let server = MockServer::start().await;
+    let project = serde_json::json!({
+        "id": "prj-1",
+        "worktree": "/repos/api",
+        "vcs": "git",
+        "time": { "created": 1_700_000_000_000u64, "updated": 1_700_000_000_000u64 },
+        "sandboxes": []
+    });
+    Mock::given(method("GET"))
+        .and(path("/project"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([project])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/project/current"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(project))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let projects = client.project().list(None).await.unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].worktree, "/repos/api");
+
+    let current = client.project().current(None).await.unwrap();
+    assert_eq!(current.id, "prj-1");
+}
+
+// ---------------------------------------------------------------------------
+// Provider
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_provider_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/provider"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "all": [{
+                "id": "openai",
+                "name": "OpenAI",
+                "env": ["OPENAI_API_KEY"],
+                "models": {
+                    "gpt-5": {
+                        "id": "gpt-5",
+                        "name": "GPT-5",
+                        "release_date": "2025-08-07",
+                        "attachment": true,
+                        "reasoning": true,
+                        "temperature": false,
+                        "tool_call": true,
+                        "limit": { "context": 400_000, "output": 128_000 },
+                        "options": {}
+                    }
+                }
+            }],
+            "default": { "openai": "gpt-5" },
+            "connected": []
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let providers = client.provider().list(None).await.unwrap();
+    assert_eq!(providers.all[0].id, "openai");
+    assert!(!providers.all[0].models["gpt-5"].temperature);
+    assert_eq!(providers.default["openai"], "gpt-5");
+    assert!(providers.connected.is_empty());
+}
+
+#[tokio::test]
+async fn test_provider_oauth_flow() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/provider/auth"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "anthropic": [{ "type": "oauth", "label": "Claude Pro/Max" }]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/provider/anthropic/oauth/authorize"))
+        .and(body_json(serde_json::json!({ "method": 0 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "url": "https://claude.ai/oauth/authorize",
+            "method": "code",
+            "instructions": "Paste the authorization code here: "
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/provider/anthropic/oauth/callback"))
+        .and(body_json(serde_json::json!({ "method": 0, "code": "abc123" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let methods = client.provider().auth(None).await.unwrap();
+    assert_eq!(methods["anthropic"][0].label, "Claude Pro/Max");
+
+    let authorization = client
+        .provider()
+        .oauth_authorize("anthropic", &ProviderOauthAuthorizeParams { method: 0 }, None)
+        .await
+        .unwrap();
+    assert_eq!(authorization.method, ProviderAuthAuthorizationMethod::Code);
+
+    let ok = client
+        .provider()
+        .oauth_callback(
+            "anthropic",
+            &ProviderOauthCallbackParams { method: 0, code: Some("abc123".into()) },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(ok);
+}
+
+// ---------------------------------------------------------------------------
+// Pty
+// ---------------------------------------------------------------------------
+
+fn pty_json(id: &str, title: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "title": title,
+        "command": "bash",
+        "args": [],
+        "cwd": "/repo",
+        "status": "running",
+        "pid": 4242
+    })
+}
+
+#[tokio::test]
+async fn test_pty_create() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/pty"))
+        .and(body_json(serde_json::json!({ "command": "bash", "title": "ci" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pty_json("pty-1", "ci")))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = PtyCreateParams {
+        command: Some("bash".into()),
+        title: Some("ci".into()),
+        ..PtyCreateParams::default()
+    };
+    let pty = client.pty().create(&params, None).await.unwrap();
+    assert_eq!(pty.id, "pty-1");
+    assert_eq!(pty.title, "ci");
+}
+
+#[tokio::test]
+async fn test_pty_list_get_update_delete() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pty"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!([pty_json("pty-1", "ci")])),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pty/pty-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pty_json("pty-1", "ci")))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/pty/pty-1"))
+        .and(body_json(serde_json::json!({ "size": { "rows": 40, "cols": 120 } })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(pty_json("pty-1", "ci")))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/pty/pty-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    assert_eq!(client.pty().list(None).await.unwrap().len(), 1);
+    assert_eq!(client.pty().get("pty-1", None).await.unwrap().pid, 4242.0);
+
+    let params = PtyUpdateParams { title: None, size: Some(PtySize { rows: 40, cols: 120 }) };
+    client.pty().update("pty-1", &params, None).await.unwrap();
+    assert!(client.pty().delete("pty-1", None).await.unwrap());
+}
+
+/// Read one masked client frame, returning its first header byte and the
+/// unmasked payload.
+async fn read_client_frame(socket: &mut tokio::net::TcpStream) -> (u8, Vec<u8>) {
+    use tokio::io::AsyncReadExt;
+
+    let mut header = [0u8; 6];
+    socket.read_exact(&mut header).await.unwrap();
+    assert_eq!(header[1] & 0x80, 0x80, "client frames must be masked");
+    let len = usize::from(header[1] & 0x7F);
+    let mut payload = vec![0u8; len];
+    socket.read_exact(&mut payload).await.unwrap();
+    for (i, b) in payload.iter_mut().enumerate() {
+        *b ^= header[2 + i % 4];
+    }
+    (header[0], payload)
+}
+
+#[tokio::test]
+async fn test_pty_connect() {
+    use base64::Engine;
+    use sha1::{Digest, Sha1};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (ready, output_wanted) = tokio::sync::oneshot::channel::<()>();
+
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+
+        // Read the upgrade request headers.
+        let mut request = Vec::new();
+        let mut byte = [0u8; 1];
+        while !request.ends_with(b"\r\n\r\n") {
+            socket.read_exact(&mut byte).await.unwrap();
+            request.push(byte[0]);
+        }
+        let request = String::from_utf8(request).unwrap();
+        let key = request
+            .lines()
+            .find_map(|l| {
+                l.split_once(':').filter(|(n, _)| n.eq_ignore_ascii_case("sec-websocket-key"))
+            })
+            .map(|(_, v)| v.trim().to_owned())
+            .unwrap();
+        let request = request.to_lowercase();
+        assert!(request.starts_with("get /pty/pty-1/connect "));
+        assert!(request.contains("upgrade: websocket"));
+        assert!(request.contains("sec-websocket-version: 13"));
+
+        let mut hasher = Sha1::new();
+        hasher.update(key.as_bytes());
+        hasher.update(b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11");
+        let accept = base64::engine::general_purpose::STANDARD.encode(hasher.finalize());
+
+        socket
+            .write_all(
+                format!(
+                    "HTTP/1.1 101 Switching Protocols\r\n\
+                     connection: upgrade\r\n\
+                     upgrade: websocket\r\n\
+                     sec-websocket-accept: {accept}\r\n\r\n"
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        // A ping is answered even though the client is not reading.
+        socket.write_all(b"\x89\x02hi").await.unwrap();
+        assert_eq!(read_client_frame(&mut socket).await, (0x8A, b"hi".to_vec()));
+
+        // Terminal output, unmasked text frame.
+        output_wanted.await.unwrap();
+        socket.write_all(b"\x81\x02$ ").await.unwrap();
+
+        // Client input, masked text frame.
+        assert_eq!(read_client_frame(&mut socket).await, (0x81, b"ls\n".to_vec()));
+
+        // Close the connection; the client echoes the status code.
+        socket.write_all(b"\x88\x02\x03\xe8").await.unwrap();
+        assert_eq!(read_client_frame(&mut socket).await, (0x88, b"\x03\xe8".to_vec()));
+    });
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(format!("http://{addr}")),
+        max_retries: Some(0),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+
+    let (mut reader, writer) = client.pty().connect("pty-1", None).await.unwrap().split();
+
+    // Cancelling `recv` before output arrives must not lose it.
+    tokio::select! {
+        _ = reader.recv() => panic!("no output expected yet"),
+        () = tokio::time::sleep(std::time::Duration::from_millis(50)) => {}
+    }
+    ready.send(()).unwrap();
+    let output = reader.recv().await.unwrap().unwrap();
+    assert_eq!(&output[..], b"$ ");
+    writer.send("ls\n").await.unwrap();
+    assert!(reader.recv().await.is_none());
+
+    server.await.unwrap();
+}
+
+// ---------------------------------------------------------------------------
+// Question
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_question_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/question"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "id": "que-1",
+            "sessionID": "sess-1",
+            "questions": [{
+                "question": "Which package manager?",
+                "header": "Package manager",
+                "options": [
+                    { "label": "npm", "description": "Default" },
+                    { "label": "pnpm", "description": "Fast, disk-efficient" }
+                ]
+            }]
+        }])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let pending = client.question().list(None).await.unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].questions[0].options[1].label, "pnpm");
+}
+
+#[tokio::test]
+async fn test_question_reply() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/question/que-1/reply"))
+        .and(body_json(serde_json::json!({ "answers": [["pnpm"]] })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = QuestionReplyParams { answers: vec![vec!["pnpm".into()]] };
+    let ok = client.question().reply("que-1", &params, None).await.unwrap();
+    assert!(ok);
+}
+
+#[tokio::test]
+async fn test_question_reject() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/question/que-1/reject"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let ok = client.question().reject("que-1", None).await.unwrap();
+    assert!(ok);
+}
+
+// ---------------------------------------------------------------------------
+// File
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_file_read() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/file/content"))
+        .and(query_param("path", "src/main.rs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "content": "fn main() {}",
+            "type": "text"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let resp =
+        client.file().read(&FileReadParams { path: "src/main.rs".to_owned() }).await.unwrap();
+    assert_eq!(resp.content, "fn main() {}");
+}
+
+#[tokio::test]
+async fn test_file_list() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/file"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "name": "main.rs", "path": "src/main.rs", "absolute": "/home/user/project/src/main.rs", "type": "file", "ignored": false },
+            { "name": "target", "path": "target", "absolute": "/home/user/project/target", "type": "directory", "ignored": true }
+        ])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = FileListParams { path: ".".into() };
+    let files = client.file().list(Some(&params)).await.unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].name, "main.rs");
+    assert!(!files[0].ignored);
+    assert!(files[1].ignored);
+}
+
+#[tokio::test]
+async fn test_file_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/file/status"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            { "added": 10, "path": "src/lib.rs", "removed": 2, "status": "modified" }
+        ])))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let files = client.file().status().await.unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "src/lib.rs");
+}
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_config_get() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/config"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "theme": "dark",
+            "autoupdate": true
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let config = client.config().get(None).await.unwrap();
+    assert_eq!(config.theme.as_deref(), Some("dark"));
+    assert_eq!(config.autoupdate, Some(serde_json::Value::Bool(true)));
+}
+
+#[tokio::test]
+async fn test_config_update() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/config"))
+        .and(body_json(serde_json::json!({ "model": "openai/gpt-5", "theme": "tokyonight" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "model": "openai/gpt-5",
+            "theme": "tokyonight",
+            "username": "dev"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let patch = ConfigPatch::new().model("openai/gpt-5").theme("tokyonight");
+    let config = client.config().update(&patch, None).await.unwrap();
+    assert_eq!(config.model.as_deref(), Some("openai/gpt-5"));
+    assert_eq!(config.username.as_deref(), Some("dev"));
+}
+
+// ---------------------------------------------------------------------------
+// Tui
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_tui_remote_control() {
+    let server = MockServer::start().await;
+    for route in ["/tui/submit-prompt", "/tui/clear-prompt", "/tui/open-models"] {
+        Mock::given(method("POST"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_json(true))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/tui/show-toast"))
+        .and(body_json(serde_json::json!({ "message": "Deployed", "variant": "success" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/tui/select-session"))
+        .and(query_param("directory", "/work/api"))
+        .and(body_json(serde_json::json!({ "sessionID": "ses_1" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let tui = client.tui();
+    assert!(tui.clear_prompt(None).await.unwrap());
+    assert!(tui.submit_prompt(None).await.unwrap());
+    assert!(tui.open_models(None).await.unwrap());
+
+    let toast = TuiShowToastParams {
+        title: None,
+        message: "Deployed".into(),
+        variant: ToastVariant::Success,
+        duration: None,
+    };
+    assert!(tui.show_toast(&toast, None).await.unwrap());
+    let options = RequestOptions { directory: Some("/work/api".into()), ..Default::default() };
+    let params = TuiSelectSessionParams { session_id: "ses_1".into() };
+    assert!(tui.select_session(&params, Some(&options)).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_tui_control_round_trip() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tui/control/next"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "path": "/tui/open-help",
+            "body": {}
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/tui/control/response"))
+        .and(body_json(serde_json::json!({ "handled": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let request = client.tui().control_next(None).await.unwrap();
+    assert_eq!(request.path, "/tui/open-help");
+
+    let ok =
+        client.tui().control_response(&serde_json::json!({ "handled": true }), None).await.unwrap();
+    assert!(ok);
+}
+
+#[tokio::test]
+async fn test_tui_control_next_does_not_retry_timeouts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/tui/control/next"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "path": "/tui/open-help", "body": {} }))
+                .set_delay(std::time::Duration::from_secs(5)),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(server.uri()),
+        max_retries: Some(2),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+    let options = RequestOptions {
+        timeout: Some(std::time::Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let err = client.tui().control_next(Some(&options)).await.unwrap_err();
+    assert!(matches!(err, OpencodeError::Timeout));
+}
+
+// ---------------------------------------------------------------------------
+// Error handling
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_error_404() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/app"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("not found"))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let err = client.app().get(None).await.unwrap_err();
+    assert_eq!(err.status(), Some(404));
+}
+
+#[tokio::test]
+async fn test_error_500() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/app"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("server error"))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let err = client.app().get(None).await.unwrap_err();
+    assert_eq!(err.status(), Some(500));
+    assert!(err.is_retryable());
+}
+
+// ---------------------------------------------------------------------------
+// Retry behaviour
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_retry_on_429() {
+    let server = MockServer::start().await;
+
+    // First request returns 429, second returns 200.
     Mock::given(method("GET"))
         .and(path("/app"))
         .respond_with(ResponseTemplate::new(429).set_body_string("rate limited"))
         .up_to_n_times(1)
         .mount(&server)
         .await;
-    Mock::given(method("GET"))
-        .and(path("/app"))
+    Mock::given(method("GET"))
+        .and(path("/app"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "git": false,
+            "hostname": "retry-host",
+            "path": { "config": "/c", "cwd": "/w", "data": "/d", "root": "/r", "state": "/s" },
+            "time": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(server.uri()),
+        timeout: Some(std::time::Duration::from_secs(10)),
+        max_retries: Some(2),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+
+    let app = client.app().get(None).await.unwrap();
+    assert_eq!(app.hostname, "retry-host");
+}
+
+// ---------------------------------------------------------------------------
+// Timeout
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/app"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({
+                    "git": true,
+                    "hostname": "h",
+                    "path": { "config": "", "cwd": "", "data": "", "root": "", "state": "" },
+                    "time": {}
+                }))
+                .set_delay(std::time::Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(server.uri()),
+        timeout: Some(std::time::Duration::from_millis(100)),
+        max_retries: Some(0),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+
+    let err = client.app().get(None).await.unwrap_err();
+    assert!(err.is_timeout());
+}
+
+// ---------------------------------------------------------------------------
+// Cancellation
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_cancel_chat_aborts_session() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/session/sess-1/message"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({}))
+                .set_delay(std::time::Duration::from_secs(30)),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/session/sess-1/abort"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let token = CancellationToken::new();
+    let opts =
+        RequestOptions { cancel: Some(token.clone()), abort_on_cancel: true, ..Default::default() };
+    let params: SessionChatParams =
+        serde_json::from_value(serde_json::json!({ "parts": [] })).unwrap();
+
+    let stop = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        token.cancel();
+    });
+    let err = client.session().chat("sess-1", &params, Some(&opts)).await.unwrap_err();
+    stop.await.unwrap();
+    assert!(matches!(err, OpencodeError::UserAbort));
+}
+
+#[tokio::test]
+async fn test_cancel_interrupts_retry_backoff() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/app"))
+        .respond_with(ResponseTemplate::new(503).insert_header("retry-after", "30"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(server.uri()),
+        max_retries: Some(2),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+    let token = CancellationToken::new();
+    let opts = RequestOptions { cancel: Some(token.clone()), ..Default::default() };
+
+    let stop = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        token.cancel();
+    });
+    let started = std::time::Instant::now();
+    let err = client.app().get(Some(&opts)).await.unwrap_err();
+    stop.await.unwrap();
+    assert!(matches!(err, OpencodeError::UserAbort));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_cancel_event_stream_connect_and_reconnect() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(": connected\n\n")
+                .set_delay(std::time::Duration::from_millis(300)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+
+    // Cancelling while connecting.
+    let token = CancellationToken::new();
+    let opts = RequestOptions { cancel: Some(token.clone()), ..Default::default() };
+    let stop = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        token.cancel();
+    });
+    let result = client.event().list(Some(&opts)).await;
+    stop.await.unwrap();
+    assert!(matches!(result, Err(OpencodeError::UserAbort)));
+
+    // Cancelling during the reconnect back-off ends the stream.
+    let token = CancellationToken::new();
+    let opts = RequestOptions { cancel: Some(token.clone()), ..Default::default() };
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_secs(30),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client.event().list_reconnecting(reconnect, Some(&opts)).await.unwrap();
+    let stop = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        token.cancel();
+    });
+    let started = std::time::Instant::now();
+    let item = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await;
+    stop.await.unwrap();
+    assert!(matches!(item, Some(Err(OpencodeError::UserAbort))));
+    assert!(poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.is_none());
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
+
+// ---------------------------------------------------------------------------
+// Middleware
+// ---------------------------------------------------------------------------
+
+/// Signs every request and records each response status it sees.
+struct Recorder {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware for Recorder {
+    fn on_request<'a>(
+        &'a self,
+        request: &'a mut MiddlewareRequest,
+    ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+        Box::pin(async move {
+            self.log.lock().unwrap().push(format!("{} request {}", self.name, request.method));
+            request.headers.insert("x-signature", http::HeaderValue::from_static("signed"));
+            Ok(())
+        })
+    }
+
+    fn on_response<'a>(
+        &'a self,
+        _request: &'a MiddlewareRequest,
+        response: &'a mut MiddlewareResponse,
+    ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+        Box::pin(async move {
+            self.log.lock().unwrap().push(format!("{} response {}", self.name, response.status));
+            Ok(())
+        })
+    }
+}
+
+/// Rejects every request before it is sent.
+struct Deny;
+
+impl Middleware for Deny {
+    fn on_request<'a>(
+        &'a self,
+        _request: &'a mut MiddlewareRequest,
+    ) -> BoxFuture<'a, Result<(), OpencodeError>> {
+        Box::pin(async { Err(OpencodeError::Http("denied by middleware".into())) })
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_wraps_json_and_stream_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/session/abc"))
+        .and(header("x-signature", "signed"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(header("x-signature", "signed"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(
+                    "data: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+                ),
+        )
+        .mount(&server)
+        .await;
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = Opencode::builder()
+        .base_url(server.uri())
+        .max_retries(0)
+        .middleware(Recorder { name: "outer", log: Arc::clone(&log) })
+        .middleware(Recorder { name: "inner", log: Arc::clone(&log) })
+        .build()
+        .unwrap();
+
+    assert!(client.session().delete("abc", None).await.unwrap());
+    let mut events = client.event().list(None).await.unwrap();
+    let event = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(event, EventListResponse::VcsBranchUpdated { .. }));
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "outer request DELETE",
+            "inner request DELETE",
+            "inner response 200 OK",
+            "outer response 200 OK",
+            "outer request GET",
+            "inner request GET",
+            "inner response 200 OK",
+            "outer response 200 OK",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_middleware_error_short_circuits_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/session"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = Opencode::builder().base_url(server.uri()).middleware(Deny).build().unwrap();
+    let err = client.session().list(None).await.unwrap_err();
+    assert!(matches!(err, OpencodeError::Http(ref e) if e.to_string() == "denied by middleware"));
+}
+
+// ---------------------------------------------------------------------------
+// Worktree
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_worktree_create_list_remove() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/experimental/worktree"))
+        .and(body_json(serde_json::json!({ "name": "agent-1" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "name": "agent-1",
+            "branch": "opencode/agent-1",
+            "directory": "/wt/agent-1"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/experimental/worktree"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(["/wt/agent-1"])))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/experimental/worktree"))
+        .and(body_json(serde_json::json!({ "directory": "/wt/agent-1" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(true))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let params = WorktreeCreateParams { name: Some("agent-1".into()), start_command: None };
+    let wt = client.worktree().create(&params, None).await.unwrap();
+    assert_eq!(wt.branch, "opencode/agent-1");
+
+    assert_eq!(client.worktree().list(None).await.unwrap(), vec!["/wt/agent-1".to_owned()]);
+
+    let params = WorktreeDirectoryParams { directory: wt.directory };
+    assert!(client.worktree().remove(&params, None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_worktree_create_and_await_ready() {
+    let server = MockServer::start().await;
+    let body = concat!(
+        "data: {\"type\":\"worktree.ready\",\"properties\":{\"name\":\"other\",\"branch\":\"opencode/other\"}}\n\n",
+        "data: {\"type\":\"worktree.ready\",\"properties\":{\"name\":\"agent-1\",\"branch\":\"opencode/agent-1\"}}\n\n",
+    );
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/experimental/worktree"))
         .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-            "git": false,
-            "hostname": "retry-host",
-            "path": { "config": "/c", "cwd": "/w", "data": "/d", "root": "/r", "state": "/s" },
-            "time": {}
+            "name": "agent-1",
+            "branch": "opencode/agent-1",
+            "directory": "/wt/agent-1"
         })))
         .mount(&server)
         .await;
 
+    let client = client_for(&server);
+    let params = WorktreeCreateParams { name: Some("agent-1".into()), start_command: None };
+    let (wt, setup) = client
+        .worktree()
+        .create_and_await_ready(&params, std::time::Duration::from_secs(5), None)
+        .await
+        .unwrap();
+    assert_eq!(wt.directory, "/wt/agent-1");
+    assert_eq!(setup, WorktreeSetup::Ready);
+
+    // The event stream is subscribed before the worktree is created.
+    let requests = server.received_requests().await.unwrap();
+    let order: Vec<_> = requests.iter().map(|r| r.method.as_str()).collect();
+    assert_eq!(order, ["GET", "POST"]);
+}
+
+#[tokio::test]
+async fn test_worktree_create_and_await_ready_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(": connected\n\n")
+                .set_delay(std::time::Duration::from_secs(5)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let err = client
+        .worktree()
+        .create_and_await_ready(
+            &WorktreeCreateParams::default(),
+            std::time::Duration::from_millis(100),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OpencodeError::Timeout));
+}
+
+#[tokio::test]
+async fn test_event_stream_survives_unknown_events() {
+    let server = MockServer::start().await;
+    let body = concat!(
+        "data: {\"type\":\"server.shiny_new_event\",\"properties\":{\"x\":1}}\n\n",
+        "data: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+    );
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let mut events = client.event().list(None).await.unwrap();
+
+    let first = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(
+        first,
+        EventListResponse::Unknown { ref event_type, .. } if event_type == "server.shiny_new_event"
+    ));
+    let second = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(second, EventListResponse::VcsBranchUpdated { .. }));
+}
+
+#[tokio::test]
+async fn test_raw_stream_keeps_event_type_and_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(
+                "event: message\nid: 1\ndata: {\"type\":\"server.connected\",\"properties\":{}}\n\n: ping\n\nid: 2\n\n",
+            ),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let mut raw = client.get_raw_stream("/event", None).await.unwrap();
+    let first = poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.unwrap().unwrap();
+    assert_eq!(
+        first,
+        ServerSentEvent {
+            event: Some("message".to_owned()),
+            data: "{\"type\":\"server.connected\",\"properties\":{}}".to_owned(),
+            id: Some("1".to_owned()),
+        }
+    );
+    let second = poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.unwrap().unwrap();
+    assert!(second.data.is_empty());
+    assert_eq!(raw.last_event_id(), Some("2"));
+    assert!(poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.is_none());
+
+    // The typed stream is layered on the raw one and tracks the same ID.
+    let raw = client.get_raw_stream("/event", None).await.unwrap();
+    let mut events = SseStream::<EventListResponse>::from(raw);
+    let event = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(event, EventListResponse::ServerConnected { .. }));
+    assert_eq!(events.last_event_id(), Some("1"));
+    assert!(poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.is_none());
+    assert_eq!(events.last_event_id(), Some("2"));
+}
+
+/// Accept one connection on `listener`, read the request head and start an
+/// event stream that sends `body` and then stalls without closing.
+async fn stalled_event_stream(
+    listener: &tokio::net::TcpListener,
+    body: &str,
+) -> (tokio::net::TcpStream, String) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut request = Vec::new();
+    let mut byte = [0u8; 1];
+    while !request.ends_with(b"\r\n\r\n") {
+        socket.read_exact(&mut byte).await.unwrap();
+        request.push(byte[0]);
+    }
+    socket
+        .write_all(
+            b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    socket.write_all(body.as_bytes()).await.unwrap();
+    (socket, String::from_utf8(request).unwrap().to_lowercase())
+}
+
+#[tokio::test]
+async fn test_event_stream_idle_timeout() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (socket, _) = stalled_event_stream(&listener, "id: 1\ndata: {}\n\n: ping\n\n").await;
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        drop(socket);
+    });
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(format!("http://{addr}")),
+        max_retries: Some(0),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+    let opened = std::time::Instant::now();
+    let mut raw = client
+        .get_raw_stream("/event", None)
+        .await
+        .unwrap()
+        .idle_timeout(std::time::Duration::from_millis(200));
+    let event = poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.unwrap().unwrap();
+    assert_eq!(event.id.as_deref(), Some("1"));
+    assert!(raw.last_activity() >= opened);
+
+    let err = poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.unwrap().unwrap_err();
+    assert!(err.is_timeout());
+    assert!(raw.last_activity().elapsed() >= std::time::Duration::from_millis(200));
+    assert!(poll_fn(|cx| Pin::new(&mut raw).poll_next(cx)).await.is_none());
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_reconnecting_event_stream_idle_timeout_reconnects() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (_first, _) = stalled_event_stream(
+            &listener,
+            "id: 1\ndata: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+        )
+        .await;
+        let (_second, request) = stalled_event_stream(
+            &listener,
+            "id: 2\ndata: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+        )
+        .await;
+        assert!(request.contains("last-event-id: 1"));
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    });
+
     let client = Opencode::with_options(&ClientOptions {
-        base_url: Some(server.uri()),
-        timeout: Some(std::time::Duration::from_secs(10)),
-        max_retries: Some(2),
+        base_url: Some(format!("http://{addr}")),
+        max_retries: Some(0),
         ..ClientOptions::empty()
     })
     .unwrap();
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client
+        .event()
+        .list_reconnecting(reconnect, None)
+        .await
+        .unwrap()
+        .idle_timeout(std::time::Duration::from_millis(200));
 
-    let app = client.app().get(None).await.unwrap();
-    assert_eq!(app.hostname, "retry-host");
+    let first = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(first, SseItem::Event(EventListResponse::ServerConnected { .. })));
+    let reconnected = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert_eq!(reconnected, SseItem::Reconnected);
+    let second = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap();
+    assert!(matches!(second, SseItem::Event(EventListResponse::ServerConnected { .. })));
+    assert_eq!(events.last_event_id(), Some("2"));
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_reconnecting_event_stream_times_out_unresponsive_reconnect() {
+    use tokio::io::AsyncReadExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (first, _) = stalled_event_stream(
+            &listener,
+            "id: 1\ndata: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+        )
+        .await;
+        drop(first);
+
+        // Accept the reconnection but never answer it.
+        let (mut silent, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = silent.read(&mut buf).await;
+
+        let (_third, request) = stalled_event_stream(
+            &listener,
+            "id: 2\ndata: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+        )
+        .await;
+        assert!(request.contains("last-event-id: 1"));
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    });
+
+    let client = Opencode::with_options(&ClientOptions {
+        base_url: Some(format!("http://{addr}")),
+        max_retries: Some(0),
+        ..ClientOptions::empty()
+    })
+    .unwrap();
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client
+        .event()
+        .list_reconnecting(reconnect, None)
+        .await
+        .unwrap()
+        .idle_timeout(std::time::Duration::from_millis(200));
+
+    let mut items = Vec::new();
+    while items.len() < 3 {
+        items.push(poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await.unwrap().unwrap());
+    }
+    assert!(matches!(items[0], SseItem::Event(EventListResponse::ServerConnected { .. })));
+    assert_eq!(items[1], SseItem::Reconnected);
+    assert!(matches!(items[2], SseItem::Event(EventListResponse::ServerConnected { .. })));
+    assert_eq!(events.last_event_id(), Some("2"));
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_event_stream_reconnects_with_last_event_id() {
+    async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(
+                "retry: 10\nid: 7\ndata: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+            ),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(header("last-event-id", "7"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(
+                "id: 8\ndata: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"dev\"}}\n\n",
+            ),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        max_attempts: Some(2),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client.event().list_reconnecting(reconnect, None).await.unwrap();
+
+    let branch = |item: Option<Result<SseItem<EventListResponse>, OpencodeError>>| match item {
+        Some(Ok(SseItem::Event(EventListResponse::VcsBranchUpdated { properties }))) => {
+            properties.branch
+        }
+        other => panic!("expected branch event, got {other:?}"),
+    };
+    assert_eq!(branch(next(&mut events).await).as_deref(), Some("main"));
+    assert!(matches!(next(&mut events).await, Some(Ok(SseItem::Reconnected))));
+    assert_eq!(branch(next(&mut events).await).as_deref(), Some("dev"));
+
+    // Further reconnects hit the 503 mock until `max_attempts` runs out.
+    assert!(matches!(next(&mut events).await, Some(Err(OpencodeError::Api { status: 503, .. }))));
+    assert!(next(&mut events).await.is_none());
+    assert_eq!(events.last_event_id(), Some("8"));
+}
+
+#[tokio::test]
+async fn test_event_stream_reconnect_drops_truncated_event() {
+    async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(concat!(
+                "id: 4\ndata: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+                "id: 5\ndata: {\"type\":\"vcs.branch.upd",
+            )),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .and(header("last-event-id", "4"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(
+                "id: 5\ndata: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"dev\"}}\n\n",
+            ),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client.event().list_reconnecting(reconnect, None).await.unwrap();
+
+    assert!(matches!(next(&mut events).await, Some(Ok(SseItem::Event(_)))));
+    assert_eq!(events.last_event_id(), Some("4"));
+    // The unterminated event is dropped, not decoded, and the resume point
+    // stays at the last complete event.
+    assert!(matches!(next(&mut events).await, Some(Ok(SseItem::Reconnected))));
+    assert!(matches!(
+        next(&mut events).await,
+        Some(Ok(SseItem::Event(EventListResponse::VcsBranchUpdated { properties })))
+            if properties.branch.as_deref() == Some("dev")
+    ));
+    assert_eq!(events.last_event_id(), Some("5"));
+}
+
+#[tokio::test]
+async fn test_event_stream_reconnect_gives_up_on_empty_connections() {
+    async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(
+                "data: {\"type\":\"vcs.branch.updated\",\"properties\":{\"branch\":\"main\"}}\n\n",
+            ),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    // Every later connection is accepted and closed straight away.
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-type", "text/event-stream"))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let reconnect = ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        max_attempts: Some(3),
+        ..ReconnectOptions::default()
+    };
+    let mut events = client.event().list_reconnecting(reconnect, None).await.unwrap();
+    assert!(matches!(next(&mut events).await, Some(Ok(SseItem::Event(_)))));
+
+    let mut reconnects = 0;
+    let last = loop {
+        match next(&mut events).await {
+            Some(Ok(SseItem::Reconnected)) => reconnects += 1,
+            other => break other,
+        }
+    };
+    assert!(matches!(last, Some(Err(OpencodeError::Connection { .. }))));
+    assert_eq!(reconnects, 3);
+    assert!(next(&mut events).await.is_none());
+}
+
+#[tokio::test]
+async fn test_worktree_await_ready_failed() {
+    let server = MockServer::start().await;
+    let body = concat!(
+        "data: {\"type\":\"worktree.failed\",\"properties\":{\"message\":\"worktree other: branch exists\"}}\n\n",
+        "data: {\"type\":\"worktree.failed\",\"properties\":{\"message\":\"worktree agent-1: start command exited with 1\"}}\n\n",
+    );
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let setup = client
+        .worktree()
+        .await_ready("agent-1", std::time::Duration::from_secs(5), None)
+        .await
+        .unwrap();
+    assert!(matches!(setup, WorktreeSetup::Failed { ref message } if message.contains("exited")));
+}
+
+#[tokio::test]
+async fn test_worktree_concurrent_waiters() {
+    let server = MockServer::start().await;
+    // One worktree fails; the other waiter must still see its own ready event.
+    let body = concat!(
+        "data: {\"type\":\"worktree.failed\",\"properties\":{\"message\":\"worktree agent-1: start command exited with 1\"}}\n\n",
+        "data: {\"type\":\"worktree.ready\",\"properties\":{\"name\":\"agent-2\",\"branch\":\"opencode/agent-2\"}}\n\n",
+    );
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(body),
+        )
+        .mount(&server)
+        .await;
+    for name in ["agent-1", "agent-2"] {
+        Mock::given(method("POST"))
+            .and(path("/experimental/worktree"))
+            .and(body_json(serde_json::json!({ "name": name })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": name,
+                "branch": format!("opencode/{name}"),
+                "directory": format!("/wt/{name}")
+            })))
+            .mount(&server)
+            .await;
+    }
+
+    let client = client_for(&server);
+    let worktrees = client.worktree();
+    let timeout = std::time::Duration::from_secs(5);
+    let first = WorktreeCreateParams { name: Some("agent-1".into()), start_command: None };
+    let second = WorktreeCreateParams { name: Some("agent-2".into()), start_command: None };
+    let (first, second) = tokio::join!(
+        worktrees.create_and_await_ready(&first, timeout, None),
+        worktrees.create_and_await_ready(&second, timeout, None),
+    );
+
+    let (wt, setup) = first.unwrap();
+    assert_eq!(wt.name, "agent-1");
+    assert!(matches!(setup, WorktreeSetup::Failed { .. }));
+    let (wt, setup) = second.unwrap();
+    assert_eq!(wt.name, "agent-2");
+    assert_eq!(setup, WorktreeSetup::Ready);
 }
 
 // ---------------------------------------------------------------------------
-// Timeout
+// Event router
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn test_timeout() {
+async fn test_event_router_filters_and_dispatches() {
     let server = MockServer::start().await;
+    // The first connection carries an undecodable event and then drops; the
+    // router skips the former and reconnects.
     Mock::given(method("GET"))
-        .and(path("/app"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200).insert_header("content-type", "text/event-stream").set_body_string(concat!(
+                "data: {\"type\":\"server.connected\",\"properties\":{}}\n\n",
+                "data: {not json}\n\n",
+                "data: {\"type\":\"message.part.delta\",\"properties\":{\"sessionID\":\"s1\",\"messageID\":\"m1\",\"partID\":\"p1\",\"field\":\"text\",\"delta\":\"Hi\"}}\n\n",
+            )),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
         .respond_with(
             ResponseTemplate::new(200)
-                .set_body_json(serde_json::json!({
-                    "git": true,
-                    "hostname": "h",
-                    "path": { "config": "", "cwd": "", "data": "", "root": "", "state": "" },
-                    "time": {}
-                }))
-                .set_delay(std::time::Duration::from_secs(5)),
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string(concat!(
+                    "data: {\"type\":\"session.idle\",\"properties\":{\"sessionID\":\"s2\"}}\n\n",
+                    "data: {\"type\":\"session.idle\",\"properties\":{\"sessionID\":\"s1\"}}\n\n",
+                )),
+        )
+        .mount(&server)
+        .await;
+
+    let shutdown = CancellationToken::new();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let record = |log: &Arc<Mutex<Vec<String>>>, entry: String| log.lock().unwrap().push(entry);
+    let router = EventRouter::new()
+        .session("s1")
+        .max_concurrency(1)
+        .reconnect(ReconnectOptions {
+            initial_delay: std::time::Duration::from_millis(10),
+            ..ReconnectOptions::default()
+        })
+        .on_session_idle({
+            let log = Arc::clone(&log);
+            let shutdown = shutdown.clone();
+            move |props| {
+                record(&log, format!("idle {}", props.session_id));
+                shutdown.cancel();
+                async {}
+            }
+        })
+        .on_message_part_delta({
+            let log = Arc::clone(&log);
+            move |props| {
+                record(&log, format!("delta {}", props.delta));
+                async {}
+            }
+        })
+        .on_event({
+            let log = Arc::clone(&log);
+            move |event| {
+                record(&log, format!("any {:?}", event.session_id()));
+                async {}
+            }
+        })
+        .on_reconnected({
+            let log = Arc::clone(&log);
+            move || {
+                record(&log, "reconnected".to_owned());
+                async {}
+            }
+        });
+
+    let client = client_for(&server);
+    router.run(&client, shutdown).await.unwrap();
+
+    let mut log = log.lock().unwrap().clone();
+    log.sort();
+    assert_eq!(
+        log,
+        ["any None", "any Some(\"s1\")", "any Some(\"s1\")", "delta Hi", "idle s1", "reconnected"]
+    );
+}
+
+#[tokio::test]
+async fn test_event_router_returns_error_when_reconnect_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_string("data: {\"type\":\"server.connected\",\"properties\":{}}\n\n"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/event"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(serde_json::json!({"error": "gone"})),
         )
         .mount(&server)
         .await;
 
+    let router = EventRouter::new().reconnect(ReconnectOptions {
+        initial_delay: std::time::Duration::from_millis(10),
+        ..ReconnectOptions::default()
+    });
+    let client = client_for(&server);
+    let result = router.run(&client, CancellationToken::new()).await;
+    assert!(matches!(result, Err(OpencodeError::Api { status: 404, .. })));
+}
+
+#[tokio::test]
+async fn test_event_router_shutdown_waits_for_handlers() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (socket, _) = stalled_event_stream(
+            &listener,
+            "data: {\"type\":\"session.idle\",\"properties\":{\"sessionID\":\"s1\"}}\n\n",
+        )
+        .await;
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        drop(socket);
+    });
+
+    let shutdown = CancellationToken::new();
+    let finished = Arc::new(Mutex::new(false));
+    let router = EventRouter::new().on_session_idle({
+        let shutdown = shutdown.clone();
+        let finished = Arc::clone(&finished);
+        move |_| {
+            let finished = Arc::clone(&finished);
+            // Stop the router while this handler is still running.
+            shutdown.cancel();
+            async move {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                *finished.lock().unwrap() = true;
+            }
+        }
+    });
+
     let client = Opencode::with_options(&ClientOptions {
-        base_url: Some(server.uri()),
-        timeout: Some(std::time::Duration::from_millis(100)),
+        base_url: Some(format!("http://{addr}")),
         max_retries: Some(0),
         ..ClientOptions::empty()
     })
     .unwrap();
-
-    let err = client.app().get(None).await.unwrap_err();
-    assert!(err.is_timeout());
+    router.run(&client, shutdown).await.unwrap();
+    assert!(*finished.lock().unwrap());
+    server.abort();
 }